@@ -0,0 +1,140 @@
+use crate::{
+	int::Integer,
+	product::ExactProduct,
+	rounding::{Remainder, RoundingMode},
+	Error,
+};
+
+/// Number of limbs of the accumulator.
+const LIMBS: usize = 70;
+
+/// Index of the limb holding the units bit.
+const UNITS: usize = 36;
+
+/// Binary exponent of the lowest accumulator bit.
+const LOWEST_EXPONENT: i32 = -(UNITS as i32) * 64;
+
+/// Wide fixed-point accumulator, holding any sum of products of `f64`
+/// values without rounding.
+///
+/// The value is stored in two's complement, in little-endian 64-bit limbs
+/// where the lowest bit has weight `2^LOWEST_EXPONENT`. This is enough to
+/// hold the product of the smallest subnormal values, and leaves more than
+/// 100 bits of headroom above the product of the largest values.
+#[derive(Debug, Clone)]
+pub(crate) struct Accumulator {
+	limbs: [u64; LIMBS],
+}
+
+impl Accumulator {
+	/// Creates a new zero accumulator.
+	pub const fn new() -> Self {
+		Self { limbs: [0; LIMBS] }
+	}
+
+	/// Adds the given product to the accumulator.
+	pub fn add(&mut self, product: ExactProduct) {
+		if product.significand == 0 {
+			return;
+		}
+
+		let position = product.exponent - LOWEST_EXPONENT;
+		debug_assert!(position >= 0, "product is too small");
+		let (index, bits) = (position as usize / 64, position as u32 % 64);
+
+		let low = product.significand << bits;
+		let high = if bits == 0 {
+			0
+		} else {
+			product.significand >> (128 - bits)
+		};
+
+		let words = [low as u64, (low >> 64) as u64, high as u64];
+		if product.negative {
+			self.sub_words(index, words)
+		} else {
+			self.add_words(index, words)
+		}
+	}
+
+	/// Adds the given words, starting at limb `index`.
+	fn add_words(&mut self, index: usize, words: [u64; 3]) {
+		let mut carry = false;
+		for (i, limb) in self.limbs[index..].iter_mut().enumerate() {
+			let word = words.get(i).copied().unwrap_or(0);
+			if i >= words.len() && !carry {
+				break;
+			}
+
+			let (sum, c1) = limb.overflowing_add(word);
+			let (sum, c2) = sum.overflowing_add(carry as u64);
+			*limb = sum;
+			carry = c1 || c2;
+		}
+	}
+
+	/// Subtracts the given words, starting at limb `index`.
+	fn sub_words(&mut self, index: usize, words: [u64; 3]) {
+		let mut borrow = false;
+		for (i, limb) in self.limbs[index..].iter_mut().enumerate() {
+			let word = words.get(i).copied().unwrap_or(0);
+			if i >= words.len() && !borrow {
+				break;
+			}
+
+			let (diff, b1) = limb.overflowing_sub(word);
+			let (diff, b2) = diff.overflowing_sub(borrow as u64);
+			*limb = diff;
+			borrow = b1 || b2;
+		}
+	}
+
+	/// Checks if the accumulated value is negative.
+	pub fn is_negative(&self) -> bool {
+		self.limbs[LIMBS - 1] >> 63 == 1
+	}
+
+	/// Splits the absolute value of the accumulator into its integer part
+	/// and the position of its fractional part.
+	///
+	/// The integer part is `None` if it does not fit in a `u128`.
+	pub fn split(&self) -> (Option<u128>, Remainder) {
+		let mut magnitude = self.limbs;
+		if self.is_negative() {
+			let mut carry = true;
+			for limb in &mut magnitude {
+				let (value, c) = (!*limb).overflowing_add(carry as u64);
+				*limb = value;
+				carry = c;
+			}
+		}
+
+		let integer = if magnitude[UNITS + 2..].iter().all(|limb| *limb == 0) {
+			Some(magnitude[UNITS] as u128 | (magnitude[UNITS + 1] as u128) << 64)
+		} else {
+			None
+		};
+
+		let half = magnitude[UNITS - 1] >> 63 == 1;
+		let sticky =
+			magnitude[UNITS - 1] << 1 != 0 || magnitude[..UNITS - 1].iter().any(|limb| *limb != 0);
+
+		(integer, Remainder::from_bits(half, sticky))
+	}
+
+	/// Rounds the accumulated value to an integer of type `T`.
+	pub fn to_int<T: Integer>(&self, mode: RoundingMode) -> Result<T, Error> {
+		let negative = self.is_negative();
+		let (integer, remainder) = self.split();
+		integer
+			.and_then(|integer| mode.round(negative, integer, remainder))
+			.and_then(|magnitude| T::from_magnitude(negative, magnitude))
+			.ok_or(Error::Overflow { negative })
+	}
+}
+
+impl Default for Accumulator {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,500 @@
+use crate::{
+	decomposed::NarrowFloat, int::Integer, rounding::RoundingMode, Decomposed, Error, Float,
+	SliceError,
+};
+
+/// Number of products computed together by the `f64` kernels.
+///
+/// Eight 64-bit lanes fill an AVX-512 register, two AVX2 registers or four
+/// NEON registers.
+const LANES: usize = 8;
+
+/// Mask of the fraction bits of an `f64`.
+const FRACTION: u64 = (1 << 52) - 1;
+
+/// Biased exponent of `f64` infinities and NaNs.
+const EXPONENT_MAX: u64 = 0x7ff;
+
+/// Offset between the biased exponent of an `f64` and the exponent of its
+/// significand's least significant bit.
+const OFFSET: i64 = 1023 + 52;
+
+/// Multiplies the elements of `a` and `b` pairwise, and stores the integer
+/// part of each product in `out`, stopping at the first failing element.
+///
+/// This is the scalar path. The three slices must have the same length.
+pub(crate) fn mul_slice<F: NarrowFloat, T: Integer>(
+	a: &[F],
+	b: &[F],
+	out: &mut [T],
+) -> Result<(), SliceError> {
+	for (index, ((a, b), out)) in a.iter().zip(b).zip(out).enumerate() {
+		*out = mul(a.decompose(), b.decompose()).map_err(|error| SliceError { index, error })?;
+	}
+
+	Ok(())
+}
+
+/// Multiplies every element of `a` by the scalar `b`, and stores the
+/// integer part of each product in `out`, stopping at the first failing
+/// element.
+///
+/// This is the scalar path. The scalar is decomposed only once.
+pub(crate) fn mul_slice_scalar<F: NarrowFloat, T: Integer>(
+	a: &[F],
+	b: F,
+	out: &mut [T],
+) -> Result<(), SliceError> {
+	let b = b.decompose();
+	for (index, (a, out)) in a.iter().zip(out).enumerate() {
+		*out = mul(a.decompose(), b).map_err(|error| SliceError { index, error })?;
+	}
+
+	Ok(())
+}
+
+/// Multiplies two decomposed values, and truncates the product to an
+/// integer of type `T`.
+fn mul<F: NarrowFloat, T: Integer>(a: Decomposed<F>, b: Decomposed<F>) -> Result<T, Error> {
+	a.mul(b)?.to_int(RoundingMode::TowardZero)
+}
+
+/// Right-hand operand of an `f64` batch multiplication.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Rhs<'a> {
+	/// Elements multiplied pairwise with the left-hand operand.
+	Slice(&'a [f64]),
+
+	/// Scalar multiplying every element of the left-hand operand.
+	Scalar(f64),
+}
+
+impl Rhs<'_> {
+	/// Returns the element multiplying the left-hand element at `index`.
+	fn get(self, index: usize) -> f64 {
+		match self {
+			Self::Slice(b) => b[index],
+			Self::Scalar(b) => b,
+		}
+	}
+
+	/// Returns the bits of the `LANES` elements starting at `start`.
+	fn lanes(self, start: usize) -> [u64; LANES] {
+		match self {
+			Self::Slice(b) => core::array::from_fn(|lane| b[start + lane].to_bits()),
+			Self::Scalar(b) => [b.to_bits(); LANES],
+		}
+	}
+}
+
+/// Vectorized version of [`mul_slice`] and [`mul_slice_scalar`] for `f64`.
+///
+/// The kernel is selected at runtime, among AVX-512 and AVX2 on `x86_64`,
+/// NEON on `aarch64`, and a portable fallback. Every kernel gives the same
+/// results as the scalar path, errors included. The slices must have the
+/// same length.
+pub(crate) fn mul_slice_f64<T: Integer>(
+	a: &[f64],
+	b: Rhs,
+	out: &mut [T],
+) -> Result<(), SliceError> {
+	Kernel::detect().mul(a, b, out)
+}
+
+/// Checks if the CPU supports the given target feature, using the given
+/// detection macro of `std::arch`.
+///
+/// Without `std`, only the features enabled at compile time are used.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! is_supported {
+	($detect:ident, $feature:tt) => {{
+		#[cfg(feature = "std")]
+		let supported = std::arch::$detect!($feature);
+		#[cfg(not(feature = "std"))]
+		let supported = cfg!(target_feature = $feature);
+		supported
+	}};
+}
+
+/// Batch kernel for `f64` products.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kernel {
+	/// Portable kernel, vectorized with the baseline features of the target.
+	Portable,
+
+	/// Portable kernel compiled with AVX2.
+	#[cfg(target_arch = "x86_64")]
+	Avx2,
+
+	/// Portable kernel compiled with AVX-512.
+	#[cfg(target_arch = "x86_64")]
+	Avx512,
+
+	/// Portable kernel compiled with NEON.
+	#[cfg(target_arch = "aarch64")]
+	Neon,
+}
+
+impl Kernel {
+	/// Every kernel of the target architecture, fastest first.
+	const ALL: &'static [Self] = &[
+		#[cfg(target_arch = "x86_64")]
+		Self::Avx512,
+		#[cfg(target_arch = "x86_64")]
+		Self::Avx2,
+		#[cfg(target_arch = "aarch64")]
+		Self::Neon,
+		Self::Portable,
+	];
+
+	/// Returns the fastest kernel supported by the CPU.
+	fn detect() -> Self {
+		Self::ALL
+			.iter()
+			.copied()
+			.find(|kernel| kernel.is_supported())
+			.unwrap_or(Self::Portable)
+	}
+
+	/// Checks if the CPU supports this kernel.
+	fn is_supported(self) -> bool {
+		match self {
+			Self::Portable => true,
+			#[cfg(target_arch = "x86_64")]
+			Self::Avx2 => is_supported!(is_x86_feature_detected, "avx2"),
+			#[cfg(target_arch = "x86_64")]
+			Self::Avx512 => is_supported!(is_x86_feature_detected, "avx512f"),
+			#[cfg(target_arch = "aarch64")]
+			Self::Neon => is_supported!(is_aarch64_feature_detected, "neon"),
+		}
+	}
+
+	/// Runs the kernel, which must be supported by the CPU.
+	fn mul<T: Integer>(self, a: &[f64], b: Rhs, out: &mut [T]) -> Result<(), SliceError> {
+		debug_assert!(self.is_supported(), "unsupported kernel {self:?}");
+		match self {
+			Self::Portable => mul_portable(a, b, out),
+			// SAFETY: the CPU supports AVX2.
+			#[cfg(target_arch = "x86_64")]
+			Self::Avx2 => unsafe { mul_avx2(a, b, out) },
+			// SAFETY: the CPU supports AVX-512.
+			#[cfg(target_arch = "x86_64")]
+			Self::Avx512 => unsafe { mul_avx512(a, b, out) },
+			// SAFETY: the CPU supports NEON.
+			#[cfg(target_arch = "aarch64")]
+			Self::Neon => unsafe { mul_neon(a, b, out) },
+		}
+	}
+}
+
+/// AVX2 version of [`mul_portable`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+fn mul_avx2<T: Integer>(a: &[f64], b: Rhs, out: &mut [T]) -> Result<(), SliceError> {
+	mul_portable(a, b, out)
+}
+
+/// AVX-512 version of [`mul_portable`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+fn mul_avx512<T: Integer>(a: &[f64], b: Rhs, out: &mut [T]) -> Result<(), SliceError> {
+	mul_portable(a, b, out)
+}
+
+/// NEON version of [`mul_portable`].
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+fn mul_neon<T: Integer>(a: &[f64], b: Rhs, out: &mut [T]) -> Result<(), SliceError> {
+	mul_portable(a, b, out)
+}
+
+/// Portable kernel, inlined into the target specific ones so that the
+/// compiler vectorizes it with their features.
+///
+/// Elements are processed by chunks of `LANES`. Products that
+/// [`Lanes::new`] leaves aside, and the elements of the last incomplete
+/// chunk, go through the scalar path.
+#[inline(always)]
+fn mul_portable<T: Integer>(a: &[f64], b: Rhs, out: &mut [T]) -> Result<(), SliceError> {
+	let scalar = |index: usize| {
+		let (a, b) = (Decomposed::<f64>::new(a[index]), b.get(index).decompose());
+		mul(a, b).map_err(|error| SliceError { index, error })
+	};
+
+	let chunks = a.chunks_exact(LANES);
+	let tail = a.len() - chunks.remainder().len();
+	for ((start, chunk), out) in (0..)
+		.step_by(LANES)
+		.zip(chunks)
+		.zip(out.chunks_exact_mut(LANES))
+	{
+		let a = core::array::from_fn(|lane| chunk[lane].to_bits());
+		let lanes = Lanes::new(&a, &b.lanes(start));
+		for (lane, out) in out.iter_mut().enumerate() {
+			*out = match lanes.to_int(lane) {
+				Some(result) => result.map_err(|error| SliceError {
+					index: start + lane,
+					error,
+				})?,
+				None => scalar(start + lane)?,
+			};
+		}
+	}
+
+	for (index, out) in out.iter_mut().enumerate().skip(tail) {
+		*out = scalar(index)?;
+	}
+
+	Ok(())
+}
+
+/// Integer parts of `LANES` products of `f64` values, computed with the
+/// same operations on every lane.
+///
+/// A lane is only computed if both values are finite and the exponent of
+/// the least significant bit of the product is not positive. The integer
+/// part is then the 106-bit product of the significands shifted right,
+/// which always fits in a `u128`.
+struct Lanes {
+	/// Low 64 bits of the integer parts.
+	low: [u64; LANES],
+
+	/// High 64 bits of the integer parts.
+	high: [u64; LANES],
+
+	/// Signs of the products.
+	negative: [bool; LANES],
+
+	/// Lanes left to the scalar path.
+	scalar: [bool; LANES],
+}
+
+impl Lanes {
+	/// Computes the integer parts of the products of the `f64` values
+	/// with the given bits.
+	#[inline(always)]
+	fn new(a: &[u64; LANES], b: &[u64; LANES]) -> Self {
+		let mut lanes = Self {
+			low: [0; LANES],
+			high: [0; LANES],
+			negative: [false; LANES],
+			scalar: [false; LANES],
+		};
+
+		for lane in 0..LANES {
+			let (a, b) = (a[lane], b[lane]);
+			let negative = (a ^ b) >> 63 == 1;
+			let (exponent_a, exponent_b) = ((a >> 52) & EXPONENT_MAX, (b >> 52) & EXPONENT_MAX);
+
+			// Significands, with the implicit bit of normal numbers, split
+			// into 32-bit limbs so that the partial products fit in 64 bits.
+			let a = (a & FRACTION) | ((exponent_a != 0) as u64) << 52;
+			let b = (b & FRACTION) | ((exponent_b != 0) as u64) << 52;
+			let (a_low, a_high) = (a & 0xffff_ffff, a >> 32);
+			let (b_low, b_high) = (b & 0xffff_ffff, b >> 32);
+
+			// The middle products are below `2^53`, and their sum below
+			// `2^54`.
+			let middle = a_low * b_high + a_high * b_low;
+			let (low, carry) = (a_low * b_low).overflowing_add(middle << 32);
+			let high = a_high * b_high + (middle >> 32) + carry as u64;
+
+			// Zero and subnormal numbers use the smallest normal exponent.
+			let exponent = exponent_a.max(1) as i64 + exponent_b.max(1) as i64 - 2 * OFFSET;
+
+			// The product is below `2^106`, so shifting it by 127 bits
+			// clears it as well as any larger shift.
+			let shift = (-exponent).clamp(0, 127) as u32;
+			let bits = shift & 63;
+			let (low, high) = if shift < 64 {
+				// Shifting in two steps avoids a 64-bit shift for `bits == 0`.
+				((low >> bits) | (high << 1) << (63 - bits), high >> bits)
+			} else {
+				(high >> bits, 0)
+			};
+
+			lanes.low[lane] = low;
+			lanes.high[lane] = high;
+			lanes.negative[lane] = negative;
+			lanes.scalar[lane] =
+				exponent_a == EXPONENT_MAX || exponent_b == EXPONENT_MAX || exponent > 0;
+		}
+
+		lanes
+	}
+
+	/// Converts the integer part of the given lane to an integer of type
+	/// `T`.
+	///
+	/// Returns `None` if the lane is left to the scalar path.
+	#[inline(always)]
+	fn to_int<T: Integer>(&self, lane: usize) -> Option<Result<T, Error>> {
+		if self.scalar[lane] {
+			None
+		} else {
+			let negative = self.negative[lane];
+			let magnitude = (self.high[lane] as u128) << 64 | self.low[lane] as u128;
+			Some(T::from_magnitude(negative, magnitude).ok_or(Error::Overflow { negative }))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::fmt::Debug;
+
+	use super::{Kernel, Rhs, FRACTION};
+	use crate::{int::Integer, test_utils::xorshift, Error, FloatMulToInt};
+
+	/// Number of elements given to a kernel at once. Restarting the kernel
+	/// after each failing element stays cheap, and the windows end with an
+	/// incomplete chunk.
+	const WINDOW: usize = 45;
+
+	/// Kernels supported by the CPU running the tests.
+	fn kernels() -> impl Iterator<Item = Kernel> {
+		Kernel::ALL
+			.iter()
+			.copied()
+			.filter(|kernel| kernel.is_supported())
+	}
+
+	/// Checks the results of `kernel` against the scalar `mul_to_int_as` on
+	/// every element, restarting it after each failing element.
+	fn check<T: Integer + PartialEq + Debug>(kernel: Kernel, a: &[f64], b: Rhs) {
+		let mut out = vec![T::ZERO; a.len()];
+		for start in (0..a.len()).step_by(WINDOW) {
+			let end = a.len().min(start + WINDOW);
+			let mut index = start;
+			while index < end {
+				let b = match b {
+					Rhs::Slice(b) => Rhs::Slice(&b[index..end]),
+					Rhs::Scalar(b) => Rhs::Scalar(b),
+				};
+
+				let (stop, error) = match kernel.mul(&a[index..end], b, &mut out[index..end]) {
+					Ok(()) => (end, None),
+					Err(e) => (index + e.index, Some(e.error)),
+				};
+
+				for (i, out) in out.iter().enumerate().take(stop).skip(index) {
+					let b = b.get(i - index);
+					assert_eq!(
+						Ok(*out),
+						a[i].mul_to_int_as::<T>(b),
+						"{kernel:?}: {} * {b}",
+						a[i]
+					);
+				}
+
+				if let Some(error) = error {
+					let b = b.get(stop - index);
+					assert_eq!(
+						Err::<T, Error>(error),
+						a[stop].mul_to_int_as::<T>(b),
+						"{kernel:?}: {} * {b}",
+						a[stop]
+					);
+				}
+
+				index = stop + 1;
+			}
+		}
+	}
+
+	/// Checks every kernel with every output type on the given operands.
+	fn check_all(a: &[f64], b: Rhs) {
+		for kernel in kernels() {
+			check::<i128>(kernel, a, b);
+			check::<u128>(kernel, a, b);
+			check::<i64>(kernel, a, b);
+			check::<u8>(kernel, a, b);
+		}
+	}
+
+	/// Boundary values: zeros, subnormals, powers of two and their
+	/// neighbours around the limits of the integer types and of the
+	/// kernels, infinities and NaN.
+	fn boundaries() -> Vec<f64> {
+		let mut values = vec![
+			0.0,
+			f64::from_bits(1),
+			f64::from_bits(FRACTION),
+			f64::MAX,
+			f64::INFINITY,
+			f64::NAN,
+			0.1,
+			1.5,
+			255.5,
+		];
+
+		for exponent in [
+			-1022, -600, -106, -64, -53, -52, -1, 0, 7, 8, 26, 52, 53, 63, 64, 105, 106, 127, 128,
+			600,
+		] {
+			let power = f64::from_bits(((exponent + 1023) as u64) << 52);
+			values.extend([power, power.next_down(), power.next_up()]);
+		}
+
+		let negated: Vec<f64> = values.iter().map(|value| -value).collect();
+		values.extend(negated);
+		values
+	}
+
+	/// Pseudo-random values, mixing raw bit patterns with values whose
+	/// products are around the limits of the kernels.
+	fn random(n: usize, seed: u64) -> Vec<f64> {
+		xorshift(seed)
+			.take(n)
+			.enumerate()
+			.map(|(i, bits)| {
+				if i % 4 == 0 {
+					f64::from_bits(bits)
+				} else {
+					let value = (bits >> 11) as f64 * 2f64.powi((bits % 80) as i32 - 60);
+					if bits & 1 == 0 {
+						value
+					} else {
+						-value
+					}
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_detect() {
+		assert!(Kernel::detect().is_supported());
+		assert_eq!(Kernel::ALL.last(), Some(&Kernel::Portable));
+	}
+
+	#[test]
+	fn test_boundaries() {
+		let values = boundaries();
+		let (a, b): (Vec<f64>, Vec<f64>) = values
+			.iter()
+			.flat_map(|a| values.iter().map(move |b| (*a, *b)))
+			.unzip();
+
+		check_all(&a, Rhs::Slice(&b));
+		for b in &values {
+			check_all(&values, Rhs::Scalar(*b));
+		}
+	}
+
+	#[test]
+	fn test_random() {
+		let (a, b) = (
+			random(10_000, 0x9e37_79b9_7f4a_7c15),
+			random(10_000, 0x2545_f491_4f6c_dd1d),
+		);
+		check_all(&a, Rhs::Slice(&b));
+		for b in &b[..8] {
+			check_all(&a, Rhs::Scalar(*b));
+		}
+
+		for n in 0..=2 * super::LANES + 1 {
+			check_all(&a[..n], Rhs::Slice(&b[..n]));
+		}
+	}
+}
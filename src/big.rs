@@ -0,0 +1,225 @@
+use core::fmt;
+
+use crate::{int::Integer, product::ExactProduct};
+
+/// Arbitrary precision integer.
+///
+/// This is the output type of
+/// [`mul_to_int_big`](crate::FloatMulToInt::mul_to_int_big), able to hold
+/// the integer part of the product of any two finite floats.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+	/// Sign bit.
+	///
+	/// False is positive, true is negative. Zero is always positive.
+	negative: bool,
+
+	/// Absolute value, as little-endian 64-bit limbs without trailing zero
+	/// limbs.
+	limbs: Vec<u64>,
+}
+
+impl BigInt {
+	/// Creates a new integer from its sign and little-endian limbs.
+	pub fn from_limbs(negative: bool, limbs: Vec<u64>) -> Self {
+		let mut result = Self { negative, limbs };
+		result.normalize();
+		result
+	}
+
+	/// Removes trailing zero limbs and the sign of zero.
+	fn normalize(&mut self) {
+		while self.limbs.last() == Some(&0) {
+			self.limbs.pop();
+		}
+
+		if self.limbs.is_empty() {
+			self.negative = false
+		}
+	}
+
+	/// Checks if the integer is negative.
+	pub fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Checks if the integer is zero.
+	pub fn is_zero(&self) -> bool {
+		self.limbs.is_empty()
+	}
+
+	/// Returns the absolute value of the integer, as little-endian 64-bit
+	/// limbs without trailing zero limbs.
+	pub fn limbs(&self) -> &[u64] {
+		&self.limbs
+	}
+
+	/// Returns the number of significant bits of the absolute value.
+	pub fn bits(&self) -> u64 {
+		match self.limbs.last() {
+			Some(last) => self.limbs.len() as u64 * 64 - last.leading_zeros() as u64,
+			None => 0,
+		}
+	}
+
+	/// Converts the integer into a primitive integer, if it fits.
+	pub fn to_int<T: Integer>(&self) -> Option<T> {
+		if self.limbs.len() > 2 {
+			return None;
+		}
+
+		let mut magnitude = 0u128;
+		for (i, limb) in self.limbs.iter().enumerate() {
+			magnitude |= (*limb as u128) << (i * 64);
+		}
+
+		T::from_magnitude(self.negative, magnitude)
+	}
+
+	/// Creates the integer part of the given product, truncating the
+	/// fractional part.
+	pub(crate) fn from_product(product: ExactProduct) -> Self {
+		if product.exponent >= 0 {
+			let exponent = product.exponent as usize;
+			let (words, bits) = (exponent / 64, exponent % 64);
+
+			let mut limbs = vec![0; words + 3];
+			let low = product.significand << bits;
+			let high = if bits == 0 {
+				0
+			} else {
+				product.significand >> (128 - bits)
+			};
+
+			limbs[words] = low as u64;
+			limbs[words + 1] = (low >> 64) as u64;
+			limbs[words + 2] = high as u64;
+
+			Self::from_limbs(product.negative, limbs)
+		} else {
+			let shift = product.exponent.unsigned_abs();
+			let magnitude = if shift >= 128 {
+				0
+			} else {
+				product.significand >> shift
+			};
+
+			Self::from_limbs(
+				product.negative,
+				vec![magnitude as u64, (magnitude >> 64) as u64],
+			)
+		}
+	}
+}
+
+impl From<i128> for BigInt {
+	fn from(value: i128) -> Self {
+		let magnitude = value.unsigned_abs();
+		Self::from_limbs(value < 0, vec![magnitude as u64, (magnitude >> 64) as u64])
+	}
+}
+
+impl From<u128> for BigInt {
+	fn from(value: u128) -> Self {
+		Self::from_limbs(false, vec![value as u64, (value >> 64) as u64])
+	}
+}
+
+impl fmt::Display for BigInt {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		/// Largest power of ten fitting in a limb.
+		const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+		// Split the absolute value into base `CHUNK` digits.
+		let mut limbs = self.limbs.clone();
+		let mut chunks = Vec::new();
+		while !limbs.is_empty() {
+			let mut remainder = 0u128;
+			for limb in limbs.iter_mut().rev() {
+				let value = remainder << 64 | *limb as u128;
+				*limb = (value / CHUNK as u128) as u64;
+				remainder = value % CHUNK as u128;
+			}
+
+			chunks.push(remainder as u64);
+
+			while limbs.last() == Some(&0) {
+				limbs.pop();
+			}
+		}
+
+		if self.negative {
+			write!(f, "-")?;
+		}
+
+		match chunks.pop() {
+			Some(first) => {
+				write!(f, "{first}")?;
+				for chunk in chunks.iter().rev() {
+					write!(f, "{chunk:019}")?;
+				}
+
+				Ok(())
+			}
+			None => write!(f, "0"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BigInt;
+	use crate::FloatMulToInt;
+
+	#[test]
+	fn test_big() {
+		let vectors = [
+			(0.0f64, -1e300f64, "0"),
+			(-0.5, 1.5, "0"),
+			(-2.5, 1.5, "-3"),
+			(1e20, 1e3, "100000000000000000000000"),
+			(
+				-(2f64.powi(200)),
+				1.0,
+				"-1606938044258990275541962092341162602522202993782792835301376",
+			),
+			(
+				f64::MAX,
+				1.0,
+				"179769313486231570814527423731704356798070567525844996598917476803157260780028538760589558632766878171540458953514382464234321326889464182768467546703537516986049910576551282076245490090389328944075868508455133942304583236903222948165808559332123348274797826204144723168738177180919299881250404026184124858368",
+			),
+		];
+
+		for (a, b, c) in vectors {
+			assert_eq!(a.mul_to_int_big(b).unwrap().to_string(), c);
+		}
+
+		let big = f64::MAX.mul_to_int_big(-f64::MAX).unwrap();
+		assert!(big.is_negative());
+		assert_eq!(big.bits(), 2048);
+		assert_eq!(big.to_int::<i128>(), None);
+
+		for (a, b) in [
+			(1.5f64, -3.0f64),
+			(2f64.powi(100), 3.25),
+			(-(2f64.powi(126)), 2.0),
+		] {
+			assert_eq!(
+				a.mul_to_int_big(b).unwrap(),
+				BigInt::from(a.mul_to_int(b).unwrap())
+			);
+		}
+
+		assert_eq!(
+			2f32.powi(127)
+				.mul_to_int_big(2f32.powi(127))
+				.unwrap()
+				.bits(),
+			255
+		);
+		assert_eq!(
+			(-0.75f32).mul_to_int_big(4.0).unwrap().to_int::<i8>(),
+			Some(-3)
+		);
+	}
+}
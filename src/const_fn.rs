@@ -0,0 +1,184 @@
+use crate::{
+	decomposed::classify_product, product::ExactProduct, rounding::RoundingMode, widen, Decomposed,
+	Error,
+};
+
+/// Multiplies the two input numbers `a` and `b`, and returns the integer
+/// part of the result *without approximation*.
+/// The fractional part is truncated.
+///
+/// This is the `const` version of
+/// [`f32::mul_to_int`](crate::FloatMulToInt::mul_to_int), returning the same
+/// result.
+///
+/// ```
+/// use fmul_to_int::mul_to_int_f32;
+///
+/// const SCALES: [f32; 4] = [0.1, 0.25, 1.5, 3.7];
+/// const TABLE: [i64; 4] = {
+///     let mut table = [0; 4];
+///     let mut i = 0;
+///     while i < SCALES.len() {
+///         table[i] = match mul_to_int_f32(SCALES[i], 1000.0) {
+///             Ok(value) => value,
+///             Err(_) => panic!("invalid scale"),
+///         };
+///         i += 1;
+///     }
+///     table
+/// };
+///
+/// assert_eq!(TABLE, [100, 250, 1500, 3700]);
+/// ```
+pub const fn mul_to_int_f32(a: f32, b: f32) -> Result<i64, Error> {
+	match mul_to_int_f64(widen(a), widen(b)) {
+		Ok(value) => {
+			if value < i64::MIN as i128 || value > i64::MAX as i128 {
+				Err(Error::Overflow {
+					negative: value < 0,
+				})
+			} else {
+				Ok(value as i64)
+			}
+		}
+		Err(e) => Err(e),
+	}
+}
+
+/// Multiplies the two input numbers `a` and `b`, and returns the integer
+/// part of the result *without approximation*.
+/// The fractional part is truncated.
+///
+/// This is the `const` version of
+/// [`f64::mul_to_int`](crate::FloatMulToInt::mul_to_int), returning the same
+/// result.
+///
+/// ```
+/// use fmul_to_int::mul_to_int_f64;
+///
+/// const MICROS: i128 = match mul_to_int_f64(1.5, 1e6) {
+///     Ok(value) => value,
+///     Err(_) => panic!("overflow"),
+/// };
+///
+/// assert_eq!(MICROS, 1_500_000);
+/// ```
+pub const fn mul_to_int_f64(a: f64, b: f64) -> Result<i128, Error> {
+	let product = match mul(Decomposed::<f64>::new(a), Decomposed::<f64>::new(b)) {
+		Ok(product) => product,
+		Err(e) => return Err(e),
+	};
+
+	match product.round_magnitude(RoundingMode::TowardZero) {
+		Some(magnitude) if magnitude <= i128::MAX as u128 => {
+			if product.negative {
+				Ok(-(magnitude as i128))
+			} else {
+				Ok(magnitude as i128)
+			}
+		}
+		Some(magnitude) if product.negative && magnitude == i128::MIN.unsigned_abs() => {
+			Ok(i128::MIN)
+		}
+		_ => Err(Error::Overflow {
+			negative: product.negative,
+		}),
+	}
+}
+
+/// Computes the exact product of two decomposed values.
+///
+/// This is the `const` version of `Decomposed::mul`.
+const fn mul(a: Decomposed<f64>, b: Decomposed<f64>) -> Result<ExactProduct, Error> {
+	/// Offset between the exponent of the decomposition and the
+	/// exponent of its significand's least significant bit.
+	const OFFSET: i32 = f64::MANTISSA_DIGITS as i32 - 1;
+
+	// `?` is not allowed in `const fn`.
+	if let Err(e) = classify_product(a.category(), b.category()) {
+		return Err(e);
+	}
+
+	Ok(ExactProduct {
+		negative: a.is_negative() ^ b.is_negative(),
+		significand: a.significand() as u128 * b.significand() as u128,
+		exponent: a.exponent() as i32 + b.exponent() as i32 - 2 * OFFSET,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{mul_to_int_f32, mul_to_int_f64, FloatMulToInt};
+
+	const TABLE: [i128; 4] = {
+		let mut table = [0; 4];
+		let mut i = 0;
+		while i < table.len() {
+			table[i] = match mul_to_int_f64(0.1 * i as f64, 1e30) {
+				Ok(value) => value,
+				Err(_) => panic!(),
+			};
+			i += 1;
+		}
+		table
+	};
+
+	#[test]
+	fn test_const() {
+		for (i, value) in TABLE.into_iter().enumerate() {
+			assert_eq!(value, (0.1 * i as f64).mul_to_int(1e30).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_f32() {
+		let values = [
+			0.0f32,
+			-0.0,
+			0.1,
+			-1.5,
+			3.7,
+			1e10,
+			-1e10,
+			2f32.powi(63),
+			-(2f32.powi(63)),
+			f32::MAX,
+			f32::MIN_POSITIVE,
+			f32::from_bits(1),
+			f32::INFINITY,
+			f32::NAN,
+		];
+
+		for a in values {
+			for b in values {
+				assert_eq!(mul_to_int_f32(a, b), a.mul_to_int(b));
+			}
+		}
+	}
+
+	#[test]
+	fn test_f64() {
+		let values = [
+			0.0f64,
+			-0.0,
+			0.1,
+			-1.5,
+			3.7,
+			1e20,
+			-1e20,
+			2f64.powi(127),
+			-(2f64.powi(127)),
+			f64::MAX,
+			f64::MIN_POSITIVE,
+			f64::from_bits(1),
+			f64::NEG_INFINITY,
+			f64::NAN,
+		];
+
+		for a in values {
+			for b in values {
+				assert_eq!(mul_to_int_f64(a, b), a.mul_to_int(b));
+			}
+		}
+	}
+}
@@ -0,0 +1,307 @@
+use core::{fmt, num::FpCategory};
+
+use crate::{
+	batch::{self, Rhs},
+	int::Integer,
+	product::ExactProduct,
+	Bf16, Error, F128Bits, SliceError, F16,
+};
+
+/// Binary floating point type that can be decomposed into its sign,
+/// exponent and significand.
+///
+/// This trait is sealed and implemented for `f32`, `f64`, [`F16`], [`Bf16`]
+/// and [`F128Bits`].
+pub trait Float: Copy + sealed::Sealed {
+	/// Unsigned integer type holding the full significand.
+	type Significand: Copy + fmt::Debug + PartialEq + Send + Sync + Into<u128>;
+
+	/// Number of bits of the significand, including the implicit `1`-bit.
+	const SIGNIFICAND_BITS: u32;
+
+	/// Decomposes the value into its sign, exponent and significand.
+	fn decompose(self) -> Decomposed<Self>;
+}
+
+mod sealed {
+	use crate::{batch, int::Integer, SliceError};
+
+	pub trait Sealed {}
+
+	/// Float type whose significand fits in 64 bits, so that the product of
+	/// two significands fits in a `u128`.
+	pub trait NarrowFloat: super::Float {
+		/// Multiplies the elements of `a` and `b` pairwise, and stores the
+		/// integer part of each product in `out`, stopping at the first
+		/// failing element.
+		fn mul_slice<T: Integer>(a: &[Self], b: &[Self], out: &mut [T]) -> Result<(), SliceError> {
+			batch::mul_slice(a, b, out)
+		}
+
+		/// Multiplies every element of `a` by the scalar `b`, and stores the
+		/// integer part of each product in `out`, stopping at the first
+		/// failing element.
+		fn mul_slice_scalar<T: Integer>(
+			a: &[Self],
+			b: Self,
+			out: &mut [T],
+		) -> Result<(), SliceError> {
+			batch::mul_slice_scalar(a, b, out)
+		}
+	}
+}
+
+pub(crate) use sealed::NarrowFloat;
+
+impl NarrowFloat for f32 {}
+impl NarrowFloat for f64 {
+	fn mul_slice<T: Integer>(a: &[f64], b: &[f64], out: &mut [T]) -> Result<(), SliceError> {
+		batch::mul_slice_f64(a, Rhs::Slice(b), out)
+	}
+
+	fn mul_slice_scalar<T: Integer>(a: &[f64], b: f64, out: &mut [T]) -> Result<(), SliceError> {
+		batch::mul_slice_f64(a, Rhs::Scalar(b), out)
+	}
+}
+impl NarrowFloat for F16 {}
+impl NarrowFloat for Bf16 {}
+
+/// Exact decomposition of a binary floating point value.
+///
+/// For finite values, the absolute value is
+/// `significand * 2^(exponent - (F::SIGNIFICAND_BITS - 1))`.
+/// The significand of normal numbers includes the implicit `1`-bit, while
+/// zero and subnormal numbers use the smallest normal exponent without
+/// implicit bit. Infinities and NaNs hold the largest exponent plus one,
+/// and their payload as significand.
+///
+/// ```
+/// use core::num::FpCategory;
+/// use fmul_to_int::Decomposed;
+///
+/// const D: Decomposed<f64> = Decomposed::<f64>::new(-1.5);
+/// assert!(D.is_negative());
+/// assert_eq!(D.exponent(), 0);
+/// assert_eq!(D.significand(), 3 << 51);
+/// assert_eq!(D.category(), FpCategory::Normal);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decomposed<F: Float> {
+	/// Sign bit.
+	///
+	/// False is positive, true is negative.
+	negative: bool,
+
+	/// Unbiased exponent.
+	exponent: i16,
+
+	/// Full significand.
+	significand: F::Significand,
+
+	/// Classification of the value.
+	category: FpCategory,
+}
+
+macro_rules! decomposed_impls {
+	($($ty:ident : $bits:ident, $significand_bits:literal, $exponent_bits:literal),*) => {
+		$(
+			impl sealed::Sealed for $ty {}
+
+			impl Float for $ty {
+				type Significand = $bits;
+
+				const SIGNIFICAND_BITS: u32 = $significand_bits;
+
+				fn decompose(self) -> Decomposed<Self> {
+					Decomposed::<$ty>::new(self)
+				}
+			}
+
+			impl Decomposed<$ty> {
+				/// Decomposes the given value.
+				pub const fn new(value: $ty) -> Self {
+					/// Bias of the exponent.
+					const BIAS: i16 = (1 << ($exponent_bits - 1)) - 1;
+
+					/// Mask of the explicit significand bits.
+					const FRACTION_MASK: $bits = (1 << ($significand_bits - 1)) - 1;
+
+					let raw = value.to_bits();
+					let negative = raw >> ($bits::BITS - 1) == 1;
+					let biased_exponent =
+						((raw >> ($significand_bits - 1)) & ((1 << $exponent_bits) - 1)) as i16;
+					let fraction = raw & FRACTION_MASK;
+
+					if biased_exponent == 0 {
+						// Zero or subnormal number, without implicit `1`-bit.
+						Self {
+							negative,
+							exponent: 1 - BIAS,
+							significand: fraction,
+							category: if fraction == 0 {
+								FpCategory::Zero
+							} else {
+								FpCategory::Subnormal
+							},
+						}
+					} else if biased_exponent as i32 == (1 << $exponent_bits) - 1 {
+						Self {
+							negative,
+							exponent: BIAS + 1,
+							significand: fraction,
+							category: if fraction == 0 {
+								FpCategory::Infinite
+							} else {
+								FpCategory::Nan
+							},
+						}
+					} else {
+						Self {
+							negative,
+							exponent: biased_exponent - BIAS,
+							significand: (1 << ($significand_bits - 1)) | fraction,
+							category: FpCategory::Normal,
+						}
+					}
+				}
+			}
+		)*
+	};
+}
+
+decomposed_impls!(f32: u32, 24, 8, f64: u64, 53, 11, F16: u16, 11, 5, Bf16: u16, 8, 8, F128Bits: u128, 113, 15);
+
+impl<F: Float> Decomposed<F> {
+	/// Checks if the sign bit is set.
+	pub const fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Returns the unbiased exponent.
+	pub const fn exponent(&self) -> i16 {
+		self.exponent
+	}
+
+	/// Returns the full significand, including the implicit `1`-bit of
+	/// normal numbers.
+	pub const fn significand(&self) -> F::Significand {
+		self.significand
+	}
+
+	/// Returns the classification of the value.
+	pub const fn category(&self) -> FpCategory {
+		self.category
+	}
+
+	/// Checks if the value is neither infinite nor NaN.
+	pub const fn is_finite(&self) -> bool {
+		!matches!(self.category, FpCategory::Infinite | FpCategory::Nan)
+	}
+
+	/// Returns the exact value as a product with `1`.
+	///
+	/// The value must be finite.
+	pub(crate) fn to_product(self) -> ExactProduct {
+		ExactProduct {
+			negative: self.negative,
+			significand: self.significand.into(),
+			exponent: self.exponent as i32 - (F::SIGNIFICAND_BITS as i32 - 1),
+		}
+	}
+}
+
+impl<F: NarrowFloat> Decomposed<F> {
+	/// Computes the exact product of two decomposed values.
+	pub(crate) fn mul<G: NarrowFloat>(self, other: Decomposed<G>) -> Result<ExactProduct, Error> {
+		classify_product(self.category, other.category)?;
+		let (a, b) = (self.to_product(), other.to_product());
+		Ok(ExactProduct {
+			negative: a.negative ^ b.negative,
+			significand: a.significand * b.significand,
+			exponent: a.exponent + b.exponent,
+		})
+	}
+}
+
+/// Checks that the product of two values of the given categories is
+/// finite.
+///
+/// Returns an [`Error::NotANumber`] error if either value is NaN, or for
+/// infinity times zero, and an [`Error::Infinite`] error if either value is
+/// otherwise infinite.
+pub(crate) const fn classify_product(a: FpCategory, b: FpCategory) -> Result<(), Error> {
+	match (a, b) {
+		(FpCategory::Nan, _) | (_, FpCategory::Nan) => Err(Error::NotANumber),
+		(FpCategory::Infinite, FpCategory::Zero) | (FpCategory::Zero, FpCategory::Infinite) => {
+			// Infinity times zero is NaN.
+			Err(Error::NotANumber)
+		}
+		(FpCategory::Infinite, _) | (_, FpCategory::Infinite) => Err(Error::Infinite),
+		_ => Ok(()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::num::FpCategory;
+
+	use crate::Decomposed;
+
+	#[test]
+	fn test_f64() {
+		let vectors = [
+			(0.0f64, false, -1022, 0u64, FpCategory::Zero),
+			(-0.0, true, -1022, 0, FpCategory::Zero),
+			(1.0, false, 0, 1 << 52, FpCategory::Normal),
+			(-3.0, true, 1, 3 << 51, FpCategory::Normal),
+			(f64::MAX, false, 1023, (1 << 53) - 1, FpCategory::Normal),
+			(f64::MIN_POSITIVE, false, -1022, 1 << 52, FpCategory::Normal),
+			(
+				f64::MIN_POSITIVE / 2.0,
+				false,
+				-1022,
+				1 << 51,
+				FpCategory::Subnormal,
+			),
+			(-f64::from_bits(1), true, -1022, 1, FpCategory::Subnormal),
+			(f64::INFINITY, false, 1024, 0, FpCategory::Infinite),
+			(f64::NEG_INFINITY, true, 1024, 0, FpCategory::Infinite),
+		];
+
+		for (value, negative, exponent, significand, category) in vectors {
+			let d = Decomposed::<f64>::new(value);
+			assert_eq!(d.is_negative(), negative);
+			assert_eq!(d.exponent(), exponent);
+			assert_eq!(d.significand(), significand);
+			assert_eq!(d.category(), category);
+			assert_eq!(d.category(), value.classify());
+		}
+
+		assert_eq!(Decomposed::<f64>::new(f64::NAN).category(), FpCategory::Nan);
+		assert!(!Decomposed::<f64>::new(f64::NAN).is_finite());
+	}
+
+	#[test]
+	fn test_f32() {
+		let vectors = [
+			(0.0f32, false, -126, 0u32, FpCategory::Zero),
+			(1.0, false, 0, 1 << 23, FpCategory::Normal),
+			(-0.75, true, -1, 3 << 22, FpCategory::Normal),
+			(f32::MAX, false, 127, (1 << 24) - 1, FpCategory::Normal),
+			(f32::MIN_POSITIVE, false, -126, 1 << 23, FpCategory::Normal),
+			(-f32::from_bits(3), true, -126, 3, FpCategory::Subnormal),
+			(f32::INFINITY, false, 128, 0, FpCategory::Infinite),
+		];
+
+		for (value, negative, exponent, significand, category) in vectors {
+			let d = Decomposed::<f32>::new(value);
+			assert_eq!(d.is_negative(), negative);
+			assert_eq!(d.exponent(), exponent);
+			assert_eq!(d.significand(), significand);
+			assert_eq!(d.category(), category);
+			assert_eq!(d.category(), value.classify());
+		}
+
+		assert_eq!(Decomposed::<f32>::new(f32::NAN).category(), FpCategory::Nan);
+	}
+}
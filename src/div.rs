@@ -0,0 +1,253 @@
+use core::cmp::Ordering;
+
+use crate::{
+	int::Integer,
+	rounding::{Remainder, RoundingMode},
+	widen, Decomposed, Error,
+};
+
+/// Float type implementing the `div_to_int` function.
+pub trait FloatDivToInt: Sized {
+	/// Integer output type.
+	type Output: Integer;
+
+	/// Divides `a` by `b`, and returns the integer part of the real
+	/// quotient *without approximation*.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the quotient is not finite, in particular when dividing by zero.
+	fn div_to_int(self, other: Self) -> Result<Self::Output, Error> {
+		self.div_to_int_rounded(other, RoundingMode::TowardZero)
+	}
+
+	/// Divides `a` by `b`, and rounds the real quotient to an integer
+	/// *without approximation* using the given rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the quotient is not finite, in particular when dividing by zero.
+	///
+	/// ```
+	/// use fmul_to_int::{FloatDivToInt, RoundingMode};
+	///
+	/// assert_eq!((-90.0f64).div_to_int_rounded(60.0, RoundingMode::Floor).unwrap(), -2i128);
+	/// ```
+	fn div_to_int_rounded(self, other: Self, mode: RoundingMode) -> Result<Self::Output, Error>;
+}
+
+impl FloatDivToInt for f32 {
+	type Output = i64;
+
+	fn div_to_int_rounded(self, other: f32, mode: RoundingMode) -> Result<i64, Error> {
+		Quotient::new(widen(self), widen(other))?.to_int(mode)
+	}
+}
+
+impl FloatDivToInt for f64 {
+	type Output = i128;
+
+	fn div_to_int_rounded(self, other: f64, mode: RoundingMode) -> Result<i128, Error> {
+		Quotient::new(self, other)?.to_int(mode)
+	}
+}
+
+/// Exact quotient of two floating point numbers.
+#[derive(Debug, Clone, Copy)]
+struct Quotient {
+	/// Sign bit.
+	///
+	/// False is positive, true is negative.
+	negative: bool,
+
+	/// Integer part of the absolute value, or `None` if it does not fit in
+	/// a `u128`.
+	integer: Option<u128>,
+
+	/// Position of the fractional part.
+	remainder: Remainder,
+}
+
+impl Quotient {
+	/// Computes the exact quotient of `a` by `b`.
+	fn new(a: f64, b: f64) -> Result<Self, Error> {
+		let negative = a.is_sign_negative() ^ b.is_sign_negative();
+
+		if a.is_nan() || b.is_nan() || (a == 0.0 && b == 0.0) {
+			Err(Error::NotANumber)
+		} else if a.is_infinite() {
+			if b.is_infinite() {
+				Err(Error::NotANumber)
+			} else {
+				Err(Error::Infinite)
+			}
+		} else if b == 0.0 {
+			Err(Error::Infinite)
+		} else if a == 0.0 || b.is_infinite() {
+			Ok(Self {
+				negative,
+				integer: Some(0),
+				remainder: Remainder::Zero,
+			})
+		} else {
+			let (a_significand, a_exponent) = normalize(Decomposed::<f64>::new(a));
+			let (b_significand, b_exponent) = normalize(Decomposed::<f64>::new(b));
+			let exponent = a_exponent - b_exponent;
+
+			// The quotient is `a_significand / b_significand * 2^exponent`,
+			// where `a_significand / b_significand` is in `(1/2, 2)`.
+			if exponent < 0 {
+				let remainder = if exponent < -1 {
+					Remainder::BelowHalf
+				} else {
+					match a_significand.cmp(&b_significand) {
+						Ordering::Less => Remainder::BelowHalf,
+						Ordering::Equal => Remainder::Half,
+						Ordering::Greater => Remainder::AboveHalf,
+					}
+				};
+
+				Ok(Self {
+					negative,
+					integer: Some(0),
+					remainder,
+				})
+			} else {
+				let divisor = b_significand as u128;
+				let mut integer = a_significand as u128 / divisor;
+				let mut rest = a_significand as u128 % divisor;
+
+				// Long division, 64 bits at a time.
+				let mut shift = exponent as u32;
+				while shift > 0 {
+					let step = shift.min(64);
+					if integer.leading_zeros() < step {
+						return Ok(Self {
+							negative,
+							integer: None,
+							remainder: Remainder::Zero,
+						});
+					}
+
+					let dividend = rest << step;
+					integer = (integer << step) | (dividend / divisor);
+					rest = dividend % divisor;
+					shift -= step;
+				}
+
+				// The next bit of the quotient, and whether the division
+				// would go on after it.
+				let half = rest << 1 >= divisor;
+				let sticky = rest != 0 && rest << 1 != divisor;
+				let remainder = Remainder::from_bits(half, sticky);
+
+				Ok(Self {
+					negative,
+					integer: Some(integer),
+					remainder,
+				})
+			}
+		}
+	}
+
+	/// Rounds the quotient to an integer of type `T`.
+	fn to_int<T: Integer>(self, mode: RoundingMode) -> Result<T, Error> {
+		self.integer
+			.and_then(|integer| mode.round(self.negative, integer, self.remainder))
+			.and_then(|magnitude| T::from_magnitude(self.negative, magnitude))
+			.ok_or(Error::Overflow {
+				negative: self.negative,
+			})
+	}
+}
+
+/// Returns the significand of a non-zero finite decomposed value, shifted
+/// so that its highest bit is set, along with the matching exponent.
+fn normalize(value: Decomposed<f64>) -> (u64, i32) {
+	let product = value.to_product();
+	let zeros = (product.significand as u64).leading_zeros();
+	(
+		(product.significand as u64) << zeros,
+		product.exponent - zeros as i32,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{test_utils::check_rounding, Error, FloatDivToInt};
+
+	#[test]
+	fn test_f64() {
+		let vectors = [
+			(10.0f64, 2.0f64, 5i128),
+			(7.0, 2.0, 3),
+			(-7.0, 2.0, -3),
+			(1.0, 3.0, 0),
+			(0.3, 0.1, 2), // `0.3 / 0.1` is `3.0` in `f64`.
+			(4.35, 0.05, 86),
+			(0.0, -5.0, 0),
+			(1e-300, 1e300, 0),
+			(0.3f32 as f64, 0.1f32 as f64, 3),
+			(5.0, f64::INFINITY, 0),
+			(-(2f64.powi(126)), 0.5, i128::MIN),
+			(2f64.powi(100), 2f64.powi(-20), 1 << 120),
+			(f64::MIN_POSITIVE / 4.0, f64::MIN_POSITIVE / 16.0, 4),
+			(1.0, f64::from_bits(1) * 2f64.powi(960), 1 << 114),
+		];
+
+		for (a, b, c) in vectors {
+			assert_eq!(a.div_to_int(b).unwrap(), c);
+		}
+	}
+
+	#[test]
+	fn test_rounded() {
+		let vectors = [
+			(5.0f64, 2.0f64, [2i128, 2, 3, 2, 3]),
+			(7.0, 2.0, [3, 3, 4, 4, 4]),
+			(-5.0, 2.0, [-2, -3, -2, -2, -3]),
+			(1.0, 3.0, [0, 0, 1, 0, 0]),
+			(2.0, 3.0, [0, 0, 1, 1, 1]),
+			(-1.0, 2.0, [0, -1, 0, 0, -1]),
+			(1e-300, 1e300, [0, 0, 1, 0, 0]),
+			(1.0, -f64::INFINITY, [0, 0, 0, 0, 0]),
+			(0.3, 0.1, [2, 2, 3, 3, 3]),
+		];
+
+		for (a, b, expected) in vectors {
+			check_rounding(expected, |mode| a.div_to_int_rounded(b, mode).unwrap());
+			if (a as f32) as f64 == a && (b as f32) as f64 == b {
+				check_rounding(expected.map(|c| c as i64), |mode| {
+					(a as f32).div_to_int_rounded(b as f32, mode).unwrap()
+				});
+			}
+		}
+	}
+
+	#[test]
+	fn test_errors() {
+		let vectors = [
+			(f64::NAN, 1.0f64, Error::NotANumber),
+			(0.0, 0.0, Error::NotANumber),
+			(f64::INFINITY, f64::NEG_INFINITY, Error::NotANumber),
+			(1.0, 0.0, Error::Infinite),
+			(f64::INFINITY, 1.0, Error::Infinite),
+			(1e300, 1e-300, Error::Overflow { negative: false }),
+			(-1.0, f64::MIN_POSITIVE, Error::Overflow { negative: true }),
+		];
+
+		for (a, b, e) in vectors {
+			assert_eq!(a.div_to_int(b), Err(e));
+		}
+
+		assert_eq!(
+			1e30f32.div_to_int(1e-30),
+			Err(Error::Overflow { negative: false })
+		);
+	}
+}
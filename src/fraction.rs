@@ -0,0 +1,94 @@
+/// Exact fractional part of a number.
+///
+/// Its absolute value is `numerator / 2^shift`, which is always lower than
+/// `1`. The fraction is kept in lowest terms: the numerator is odd, unless
+/// the fraction is zero, in which case the shift is also zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fraction {
+	/// Sign bit.
+	///
+	/// False is positive, true is negative.
+	negative: bool,
+
+	/// Numerator.
+	numerator: u128,
+
+	/// Power of two of the denominator.
+	shift: u32,
+}
+
+impl Fraction {
+	/// Zero fraction.
+	pub const ZERO: Self = Self {
+		negative: false,
+		numerator: 0,
+		shift: 0,
+	};
+
+	/// Creates the fraction `numerator / 2^shift`, reducing it to lowest
+	/// terms.
+	pub(crate) const fn new(negative: bool, numerator: u128, shift: u32) -> Self {
+		if numerator == 0 {
+			Self::ZERO
+		} else {
+			let zeros = numerator.trailing_zeros();
+			Self {
+				negative,
+				numerator: numerator >> zeros,
+				shift: shift - zeros,
+			}
+		}
+	}
+
+	/// Checks if the fraction is negative.
+	pub const fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Checks if the fraction is zero.
+	pub const fn is_zero(&self) -> bool {
+		self.numerator == 0
+	}
+
+	/// Returns the numerator of the absolute value of the fraction.
+	pub const fn numerator(&self) -> u128 {
+		self.numerator
+	}
+
+	/// Returns the power of two of the denominator of the fraction.
+	pub const fn shift(&self) -> u32 {
+		self.shift
+	}
+
+	/// Converts the fraction into a `f32`, if it is exactly representable.
+	pub fn to_f32(&self) -> Option<f32> {
+		if self.numerator >= 1 << f32::MANTISSA_DIGITS || self.shift > 149 {
+			return None;
+		}
+
+		let scale = if self.shift <= 126 {
+			f32::from_bits((127 - self.shift) << 23)
+		} else {
+			f32::from_bits(1 << (149 - self.shift))
+		};
+
+		let value = self.numerator as f32 * scale;
+		Some(if self.negative { -value } else { value })
+	}
+
+	/// Converts the fraction into a `f64`, if it is exactly representable.
+	pub fn to_f64(&self) -> Option<f64> {
+		if self.numerator >= 1 << f64::MANTISSA_DIGITS || self.shift > 1074 {
+			return None;
+		}
+
+		let scale = if self.shift <= 1022 {
+			f64::from_bits(((1023 - self.shift) as u64) << 52)
+		} else {
+			f64::from_bits(1 << (1074 - self.shift))
+		};
+
+		let value = self.numerator as f64 * scale;
+		Some(if self.negative { -value } else { value })
+	}
+}
@@ -0,0 +1,192 @@
+use core::num::FpCategory;
+
+use crate::{Decomposed, FloatMulToInt};
+
+/// IEEE 754 binary16 value, stored as its raw bits.
+///
+/// This type only provides what is needed to multiply half-precision
+/// values without approximation, and to convert them to wider floats.
+///
+/// ```
+/// use fmul_to_int::{FloatMulToInt, F16};
+///
+/// let activation = F16::from_bits(0x5640); // 100.0
+/// let scale = F16::from_bits(0x2e66); // 0.0999755859375
+/// assert_eq!(activation.to_f32(), 100.0);
+/// assert_eq!(activation.mul_to_int(scale).unwrap(), 9i64);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct F16(u16);
+
+/// bfloat16 value, stored as its raw bits.
+///
+/// This is the upper half of an `f32`, with the same exponent range and
+/// an 8-bit significand.
+///
+/// ```
+/// use fmul_to_int::{Bf16, FloatMulToInt};
+///
+/// let activation = Bf16::from_bits(0x42c8); // 100.0
+/// let scale = Bf16::from_bits(0x3dcc); // 0.099609375
+/// assert_eq!(activation.to_f32(), 100.0);
+/// assert_eq!(activation.mul_to_int(scale).unwrap(), 9i64);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bf16(u16);
+
+macro_rules! half_impls {
+	($($ty:ident),*) => {
+		$(
+			impl $ty {
+				/// Creates a value from its raw bits.
+				pub const fn from_bits(bits: u16) -> Self {
+					Self(bits)
+				}
+
+				/// Returns the raw bits of the value.
+				pub const fn to_bits(self) -> u16 {
+					self.0
+				}
+
+				/// Converts the value to `f32`, without approximation.
+				pub fn to_f32(self) -> f32 {
+					let d = Decomposed::<$ty>::new(self);
+					let value = match d.category() {
+						FpCategory::Nan => f32::NAN,
+						FpCategory::Infinite => f32::INFINITY,
+						// Every value is exactly representable.
+						_ => d.to_product().to_f64().abs() as f32,
+					};
+
+					if d.is_negative() {
+						-value
+					} else {
+						value
+					}
+				}
+
+				/// Converts the value to `f64`, without approximation.
+				pub fn to_f64(self) -> f64 {
+					self.to_f32() as f64
+				}
+			}
+
+			impl From<$ty> for f32 {
+				fn from(value: $ty) -> f32 {
+					value.to_f32()
+				}
+			}
+
+			impl From<$ty> for f64 {
+				fn from(value: $ty) -> f64 {
+					value.to_f64()
+				}
+			}
+
+			impl FloatMulToInt for $ty {
+				type Output = i64;
+			}
+		)*
+	};
+}
+
+half_impls!(F16, Bf16);
+
+#[cfg(test)]
+mod tests {
+	use crate::{Bf16, Error, FloatMulToInt, F16};
+
+	/// Reference implementation, using the `f64` product which is exact for
+	/// half-precision operands.
+	fn expected(a: f64, b: f64) -> Result<i64, Error> {
+		let product = a * b;
+		if product.is_nan() {
+			Err(Error::NotANumber)
+		} else if product.is_infinite() {
+			Err(Error::Infinite)
+		} else if product.trunc() >= 2f64.powi(63) {
+			Err(Error::Overflow { negative: false })
+		} else if product.trunc() < -(2f64.powi(63)) {
+			Err(Error::Overflow { negative: true })
+		} else {
+			Ok(product as i64)
+		}
+	}
+
+	#[test]
+	fn test_to_f32() {
+		let vectors = [
+			(0x0000u16, 0.0f32),
+			(0x8000, -0.0),
+			(0x3c00, 1.0),
+			(0xc000, -2.0),
+			(0x7bff, 65504.0),
+			(0x0400, 2.0f32.powi(-14)),
+			(0x0001, 2.0f32.powi(-24)),
+			(0x7c00, f32::INFINITY),
+			(0xfc00, f32::NEG_INFINITY),
+		];
+
+		for (bits, value) in vectors {
+			assert_eq!(F16::from_bits(bits).to_f32().to_bits(), value.to_bits());
+		}
+
+		for bits in 0..=u16::MAX {
+			let bf16 = Bf16::from_bits(bits).to_f32();
+			let f32 = f32::from_bits((bits as u32) << 16);
+			assert!(bf16.to_bits() == f32.to_bits() || (bf16.is_nan() && f32.is_nan()));
+		}
+
+		assert!(F16::from_bits(0x7e00).to_f32().is_nan());
+	}
+
+	#[test]
+	fn test_f16_sampled() {
+		for a in (0..=u16::MAX).step_by(7) {
+			for b in (0..=u16::MAX).step_by(1009) {
+				let (a, b) = (F16::from_bits(a), F16::from_bits(b));
+				assert_eq!(a.mul_to_int(b), expected(a.to_f64(), b.to_f64()));
+			}
+		}
+	}
+
+	#[test]
+	fn test_bf16_sampled() {
+		for a in (0..=u16::MAX).step_by(7) {
+			for b in (0..=u16::MAX).step_by(1009) {
+				let (a, b) = (Bf16::from_bits(a), Bf16::from_bits(b));
+				assert_eq!(a.mul_to_int(b), expected(a.to_f64(), b.to_f64()));
+			}
+		}
+	}
+
+	/// Checks all the `2^32` pairs of `F16` values.
+	///
+	/// Run with `cargo test --release -- --ignored`.
+	#[test]
+	#[ignore]
+	fn test_f16_exhaustive() {
+		for a in 0..=u16::MAX {
+			let a = F16::from_bits(a);
+			for b in 0..=u16::MAX {
+				let b = F16::from_bits(b);
+				assert_eq!(a.mul_to_int(b), expected(a.to_f64(), b.to_f64()));
+			}
+		}
+	}
+
+	/// Checks all the `2^32` pairs of `Bf16` values.
+	///
+	/// Run with `cargo test --release -- --ignored`.
+	#[test]
+	#[ignore]
+	fn test_bf16_exhaustive() {
+		for a in 0..=u16::MAX {
+			let a = Bf16::from_bits(a);
+			for b in 0..=u16::MAX {
+				let b = Bf16::from_bits(b);
+				assert_eq!(a.mul_to_int(b), expected(a.to_f64(), b.to_f64()));
+			}
+		}
+	}
+}
@@ -0,0 +1,107 @@
+/// Primitive integer type that can hold the result of an exact conversion.
+///
+/// This trait is sealed and implemented for every primitive integer type.
+pub trait Integer: Sized + Copy + sealed::Sealed {
+	/// Zero value of this integer type.
+	const ZERO: Self;
+
+	/// Smallest value of this integer type.
+	const MIN: Self;
+
+	/// Largest value of this integer type.
+	const MAX: Self;
+
+	/// Converts the integer with the given sign and absolute value.
+	///
+	/// Returns `None` if the integer is out of range.
+	fn from_magnitude(negative: bool, magnitude: u128) -> Option<Self>;
+
+	/// Converts the integer with the given sign and absolute value, modulo
+	/// `2^N` where `N` is the number of bits of this integer type.
+	fn wrapping_from_magnitude(negative: bool, magnitude: u128) -> Self;
+
+	/// Converts the integer with the given sign and absolute value, clamping
+	/// it to the `[MIN, MAX]` range.
+	fn saturating_from_magnitude(negative: bool, magnitude: u128) -> Self {
+		match Self::from_magnitude(negative, magnitude) {
+			Some(value) => value,
+			None if negative => Self::MIN,
+			None => Self::MAX,
+		}
+	}
+}
+
+mod sealed {
+	pub trait Sealed {}
+}
+
+macro_rules! signed {
+	($($ty:ident : $unsigned:ident),*) => {
+		$(
+			impl sealed::Sealed for $ty {}
+
+			impl Integer for $ty {
+				const ZERO: Self = 0;
+
+				const MIN: Self = $ty::MIN;
+
+				const MAX: Self = $ty::MAX;
+
+				fn from_magnitude(negative: bool, magnitude: u128) -> Option<Self> {
+					if negative {
+						if magnitude <= $ty::MIN.unsigned_abs() as u128 {
+							Some((magnitude as $unsigned as $ty).wrapping_neg())
+						} else {
+							None
+						}
+					} else {
+						$ty::try_from(magnitude).ok()
+					}
+				}
+
+				fn wrapping_from_magnitude(negative: bool, magnitude: u128) -> Self {
+					if negative {
+						(magnitude as $ty).wrapping_neg()
+					} else {
+						magnitude as $ty
+					}
+				}
+			}
+		)*
+	};
+}
+
+macro_rules! unsigned {
+	($($ty:ident),*) => {
+		$(
+			impl sealed::Sealed for $ty {}
+
+			impl Integer for $ty {
+				const ZERO: Self = 0;
+
+				const MIN: Self = $ty::MIN;
+
+				const MAX: Self = $ty::MAX;
+
+				fn from_magnitude(negative: bool, magnitude: u128) -> Option<Self> {
+					if negative && magnitude != 0 {
+						None
+					} else {
+						$ty::try_from(magnitude).ok()
+					}
+				}
+
+				fn wrapping_from_magnitude(negative: bool, magnitude: u128) -> Self {
+					if negative {
+						(magnitude as $ty).wrapping_neg()
+					} else {
+						magnitude as $ty
+					}
+				}
+			}
+		)*
+	};
+}
+
+signed!(i8: u8, i16: u16, i32: u32, i64: u64, i128: u128, isize: usize);
+unsigned!(u8, u16, u32, u64, u128, usize);
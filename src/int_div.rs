@@ -0,0 +1,204 @@
+use core::num::FpCategory;
+
+use crate::{product::ExactProduct, rounding::RoundingMode, Decomposed};
+
+/// Divides the integer `n` by `scale`, and returns the nearest `f64`, ties
+/// to even.
+///
+/// This is the inverse of [`mul_to_int`](crate::FloatMulToInt::mul_to_int):
+/// the quotient is computed exactly before being rounded once, whereas
+/// `n as f64 / scale` rounds both the conversion and the division.
+///
+/// Special values follow IEEE 754 division: dividing by zero gives an
+/// infinity (or NaN if `n` is zero), and dividing by an infinity gives zero.
+///
+/// ```
+/// use fmul_to_int::int_div_to_float;
+///
+/// let nanos = (1i128 << 60) + 129;
+/// assert_eq!(int_div_to_float(nanos, 1e9), 1152921504.606847);
+/// assert_eq!(nanos as f64 / 1e9, 1152921504.6068473); // Rounded twice.
+/// ```
+pub fn int_div_to_float(n: i128, scale: f64) -> f64 {
+	int_div_to_float_rounded(n, scale, RoundingMode::NearestEven)
+}
+
+/// Divides the integer `n` by `scale`, and rounds the quotient to a `f64`
+/// using the given rounding `mode`.
+///
+/// The quotient is computed exactly before being rounded once. Quotients
+/// too large to be represented are rounded to an infinity or to the largest
+/// finite `f64`, as IEEE 754 prescribes for the rounding mode.
+///
+/// ```
+/// use fmul_to_int::{int_div_to_float_rounded, RoundingMode};
+///
+/// assert_eq!(int_div_to_float_rounded(1, 3.0, RoundingMode::Floor), 0.3333333333333333);
+/// assert_eq!(int_div_to_float_rounded(1, 3.0, RoundingMode::Ceil), 0.33333333333333337);
+/// ```
+pub fn int_div_to_float_rounded(n: i128, scale: f64, mode: RoundingMode) -> f64 {
+	let d = Decomposed::<f64>::new(scale);
+	match d.category() {
+		FpCategory::Nan => f64::NAN,
+		FpCategory::Infinite => 0.0 / scale.signum() * n.signum() as f64,
+		FpCategory::Zero if n == 0 => f64::NAN,
+		FpCategory::Zero => f64::INFINITY * scale.signum() * n.signum() as f64,
+		_ if n == 0 => 0.0 / scale,
+		_ => {
+			let divisor = d.to_product();
+			let magnitude = n.unsigned_abs();
+
+			// Shifts the dividend so that the quotient has at least 56
+			// significant bits. Rounding it to 53 bits then drops at least
+			// two bits below the rounding bit, so the lowest bit can hold
+			// the sticky bit of the division remainder. The quotient may
+			// use all 128 bits, as for `i128::MIN` divided by `1`.
+			let dividend_bits = 128 - magnitude.leading_zeros();
+			let divisor_bits = 128 - divisor.significand.leading_zeros();
+			let shift = (56 + divisor_bits).saturating_sub(dividend_bits);
+
+			let dividend = magnitude << shift;
+			let quotient = dividend / divisor.significand;
+			let sticky = !dividend.is_multiple_of(divisor.significand);
+
+			ExactProduct {
+				negative: (n < 0) ^ divisor.negative,
+				significand: quotient | sticky as u128,
+				exponent: -(shift as i32) - divisor.exponent,
+			}
+			.to_f64_rounded(mode)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cmp::Ordering;
+
+	use crate::{int_div_to_float, int_div_to_float_rounded, FloatMulToInt, RoundingMode};
+
+	/// Checks that `result` is the correctly rounded quotient of `n` by
+	/// `scale`, comparing exact products.
+	fn check(n: i128, scale: f64, mode: RoundingMode, result: f64) {
+		// Compares `x` with the exact quotient.
+		let cmp = |x: f64| {
+			let ordering = x.mul_exact(scale).unwrap().cmp_int(n);
+			if scale < 0.0 {
+				ordering.reverse()
+			} else {
+				ordering
+			}
+		};
+		let down = f64::from_bits(result.to_bits() - 1);
+		let up = f64::from_bits(result.to_bits() + 1);
+		let (below, above) = if result > 0.0 { (down, up) } else { (up, down) };
+		match mode {
+			RoundingMode::Floor => {
+				assert_ne!(cmp(result), Ordering::Greater);
+				assert_eq!(cmp(above), Ordering::Greater);
+			}
+			RoundingMode::Ceil => {
+				assert_ne!(cmp(result), Ordering::Less);
+				assert_eq!(cmp(below), Ordering::Less);
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn test_exact_division() {
+		// Integers lower than `2^53` are exact floats, and IEEE 754 division
+		// is correctly rounded.
+		let integers = [
+			1i128,
+			-1,
+			3,
+			7,
+			-10,
+			1_000_000_007,
+			(1 << 53) - 1,
+			-(1 << 53),
+		];
+		let scales = [1.0f64, 3.0, -3.0, 1e9, 0.1, 1e-300, 1e300, 7e-320, f64::MAX];
+		for n in integers {
+			for scale in scales {
+				assert_eq!(int_div_to_float(n, scale), n as f64 / scale);
+			}
+		}
+	}
+
+	#[test]
+	fn test_rounded() {
+		let integers = [
+			1i128,
+			-1,
+			3,
+			(1 << 60) + 3,
+			-(1 << 100) - 12345,
+			i128::MAX,
+			i128::MIN,
+			i128::MIN + 1,
+		];
+		let scales = [1.0f64, 3.0, -3.0, 1e9, -0.1, 1e-200, 1e300, -7e300];
+		for n in integers {
+			for scale in scales {
+				for mode in [RoundingMode::Floor, RoundingMode::Ceil] {
+					check(n, scale, mode, int_div_to_float_rounded(n, scale, mode));
+				}
+
+				let result = int_div_to_float(n, scale);
+				let floor = int_div_to_float_rounded(n, scale, RoundingMode::Floor);
+				let ceil = int_div_to_float_rounded(n, scale, RoundingMode::Ceil);
+				assert!(result == floor || result == ceil);
+			}
+		}
+	}
+
+	#[test]
+	fn test_round_trip() {
+		let values = [0.1f64, 1.5, -2.75, 1234.567890123, 1e-9, 3e6];
+		for x in values {
+			let nanos = x
+				.mul_to_int_rounded(1e9, RoundingMode::NearestEven)
+				.unwrap();
+			let seconds = int_div_to_float(nanos, 1e9);
+			assert_eq!(seconds, nanos as f64 / 1e9);
+		}
+
+		// Converting `n` to `f64` first rounds it to `2^60 + 256`.
+		let n = (1i128 << 60) + 129;
+		assert_eq!(int_div_to_float(n, 1e9), 1152921504.606847);
+		assert_eq!(int_div_to_float(n, 10.0), 1.152921504606847e17);
+		assert_eq!(int_div_to_float(n, 1.0), 2f64.powi(60) + 256.0);
+	}
+
+	#[test]
+	fn test_special() {
+		assert!(int_div_to_float(1, f64::NAN).is_nan());
+		assert!(int_div_to_float(0, 0.0).is_nan());
+		assert_eq!(int_div_to_float(5, 0.0), f64::INFINITY);
+		assert_eq!(int_div_to_float(-5, 0.0), f64::NEG_INFINITY);
+		assert_eq!(int_div_to_float(5, -0.0), f64::NEG_INFINITY);
+		assert_eq!(int_div_to_float(5, f64::INFINITY), 0.0);
+		assert!(int_div_to_float(-5, f64::INFINITY).is_sign_negative());
+		assert!(int_div_to_float(0, -1.0).is_sign_negative());
+		assert_eq!(
+			int_div_to_float(i128::MAX, f64::from_bits(1)),
+			f64::INFINITY
+		);
+		assert_eq!(
+			int_div_to_float_rounded(i128::MAX, f64::from_bits(1), RoundingMode::TowardZero),
+			f64::MAX
+		);
+		assert_eq!(
+			int_div_to_float(i128::MIN, f64::from_bits(1)),
+			f64::NEG_INFINITY
+		);
+		assert_eq!(
+			int_div_to_float(i128::MIN, -f64::from_bits(1)),
+			f64::INFINITY
+		);
+		assert_eq!(int_div_to_float(i128::MIN, 1.0), -(2f64.powi(127)));
+		assert_eq!(int_div_to_float(i128::MIN, -0.5), 2f64.powi(128));
+	}
+}
@@ -0,0 +1,179 @@
+use core::iter::FusedIterator;
+
+use crate::{rounding::RoundingMode, Decomposed, Error, Float, FloatMulToInt};
+
+/// Extension trait adding the `mul_to_int_by` adapter to iterators over
+/// floats.
+pub trait MulToIntIter: Iterator + Sized
+where
+	Self::Item: FloatMulToInt,
+{
+	/// Multiplies every item by `scale`, and yields the integer part of each
+	/// product *without approximation*, as
+	/// [`mul_to_int`](FloatMulToInt::mul_to_int) does.
+	///
+	/// The scale is decomposed only once.
+	///
+	/// ```
+	/// use fmul_to_int::MulToIntIter;
+	///
+	/// let prices = [1.5, 2.25, 10.0];
+	/// let cents: Vec<i128> = prices.into_iter().mul_to_int_by(100.0).try_collect().unwrap();
+	/// assert_eq!(cents, [150, 225, 1000]);
+	/// ```
+	fn mul_to_int_by(self, scale: Self::Item) -> MulToIntBy<Self> {
+		MulToIntBy {
+			iter: self,
+			scale: scale.decompose(),
+		}
+	}
+}
+
+impl<I: Iterator> MulToIntIter for I where I::Item: FloatMulToInt {}
+
+/// Iterator multiplying the items of another iterator by a constant scale.
+///
+/// This `struct` is created by the
+/// [`mul_to_int_by`](MulToIntIter::mul_to_int_by) method.
+#[derive(Debug, Clone)]
+pub struct MulToIntBy<I: Iterator>
+where
+	I::Item: FloatMulToInt,
+{
+	/// Underlying iterator.
+	iter: I,
+
+	/// Decomposed scale.
+	scale: Decomposed<I::Item>,
+}
+
+impl<I: Iterator> MulToIntBy<I>
+where
+	I::Item: FloatMulToInt,
+{
+	/// Collects all the integers into a collection of type `C`, stopping at
+	/// the first error.
+	pub fn try_collect<C: FromIterator<<I::Item as FloatMulToInt>::Output>>(
+		self,
+	) -> Result<C, Error> {
+		self.collect()
+	}
+
+	/// Extends `collection` with the integers, stopping at the first error.
+	///
+	/// Integers preceding the error are added to the collection.
+	pub fn try_extend<C: Extend<<I::Item as FloatMulToInt>::Output>>(
+		self,
+		collection: &mut C,
+	) -> Result<(), Error> {
+		for value in self {
+			collection.extend(Some(value?));
+		}
+
+		Ok(())
+	}
+}
+
+impl<I: Iterator> Iterator for MulToIntBy<I>
+where
+	I::Item: FloatMulToInt,
+{
+	type Item = Result<<I::Item as FloatMulToInt>::Output, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|value| {
+			value
+				.decompose()
+				.mul(self.scale)?
+				.to_int(RoundingMode::TowardZero)
+		})
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for MulToIntBy<I>
+where
+	I::Item: FloatMulToInt,
+{
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(|value| {
+			value
+				.decompose()
+				.mul(self.scale)?
+				.to_int(RoundingMode::TowardZero)
+		})
+	}
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for MulToIntBy<I> where I::Item: FloatMulToInt {}
+
+impl<I: FusedIterator> FusedIterator for MulToIntBy<I> where I::Item: FloatMulToInt {}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Error, FloatMulToInt, MulToIntIter};
+
+	#[test]
+	fn test_f64() {
+		let values = [0.0f64, -0.0, 0.1, -1.5, 3.7, 1e20, -1e30, f64::MIN_POSITIVE];
+		for scale in [0.0, 1.0, -0.1, 1e6, 3e8, f64::from_bits(1)] {
+			let expected: Vec<_> = values.iter().map(|x| x.mul_to_int(scale)).collect();
+			let result: Vec<_> = values.iter().copied().mul_to_int_by(scale).collect();
+			assert_eq!(result, expected);
+
+			let reversed: Vec<_> = values.iter().copied().mul_to_int_by(scale).rev().collect();
+			assert!(reversed.into_iter().eq(expected.into_iter().rev()));
+		}
+	}
+
+	#[test]
+	fn test_f32() {
+		let values = [0.1f32, 2.5, -7.0];
+		let result: Vec<i64> = values
+			.into_iter()
+			.mul_to_int_by(10.0)
+			.try_collect()
+			.unwrap();
+		assert_eq!(result, [1, 25, -70]);
+		assert_eq!(values.into_iter().mul_to_int_by(10.0).len(), 3);
+	}
+
+	#[test]
+	fn test_errors() {
+		let values = [1.0f64, 2.0, f64::MAX, f64::NAN, 3.0];
+		assert_eq!(
+			values
+				.into_iter()
+				.mul_to_int_by(2.0)
+				.try_collect::<Vec<_>>(),
+			Err(Error::Overflow { negative: false })
+		);
+
+		let mut result = vec![0];
+		assert_eq!(
+			values
+				.into_iter()
+				.mul_to_int_by(-2.0)
+				.try_extend(&mut result),
+			Err(Error::Overflow { negative: true })
+		);
+		assert_eq!(result, [0, -2, -4]);
+
+		let mut result = Vec::new();
+		values[..2]
+			.iter()
+			.copied()
+			.mul_to_int_by(0.5)
+			.try_extend(&mut result)
+			.unwrap();
+		assert_eq!(result, [0, 1]);
+
+		assert_eq!(
+			[f64::INFINITY].into_iter().mul_to_int_by(0.0).next(),
+			Some(Err(Error::NotANumber))
+		);
+	}
+}
@@ -12,189 +12,300 @@
 //!
 //! assert_eq!(11.0f64.mul_to_int(1_000_000_000.0).unwrap(), 11_000_000_000i128);
 //! ```
+//!
+//! Other rounding modes are available through the `mul_to_int_rounded`
+//! method.
+//!
+//! ```
+//! use fmul_to_int::{FloatMulToInt, RoundingMode};
+//!
+//! assert_eq!((-0.5f64).mul_to_int_rounded(3.0, RoundingMode::Floor).unwrap(), -2i128);
+//! ```
+//!
+//! The `FloatDivToInt` trait similarly provides the `div_to_int` method,
+//! returning the exact integer part of the quotient of two float numbers.
+//!
+//! Half-precision values are supported through the `F16` (IEEE 754
+//! binary16) and `Bf16` (bfloat16) types, built from their raw bits. The
+//! `F128Bits` type provides the `mul_to_int`, `mul_to_int_rounded`,
+//! `mul_to_int_as` and `mul_to_int_big` methods for IEEE 754 binary128
+//! values.
+
+use core::cmp::Ordering;
+
+use decomposed::NarrowFloat;
+
+mod accumulator;
+mod batch;
+mod big;
+mod const_fn;
+mod decomposed;
+mod div;
+mod fraction;
+mod half;
+mod int;
+mod int_div;
+mod iter;
+mod mixed;
+mod mul_add;
+mod mul_int;
+mod multiplier;
+#[cfg(feature = "rayon")]
+mod par;
+mod product;
+mod quad;
+mod rounding;
+mod scaled;
+mod slice;
+mod sum;
+#[cfg(test)]
+mod test_utils;
+
+pub use big::BigInt;
+pub use const_fn::{mul_to_int_f32, mul_to_int_f64};
+pub use decomposed::{Decomposed, Float};
+pub use div::FloatDivToInt;
+pub use fraction::Fraction;
+pub use half::{Bf16, F16};
+pub use int::Integer;
+pub use int_div::{int_div_to_float, int_div_to_float_rounded};
+pub use iter::{MulToIntBy, MulToIntIter};
+pub use mixed::FloatMulMixedToInt;
+pub use mul_add::FloatMulAddToInt;
+pub use mul_int::FloatMulIntToInt;
+pub use multiplier::Multiplier;
+#[cfg(feature = "rayon")]
+pub use par::{par_mul_to_int, par_mul_to_int_scalar};
+pub use product::ExactProduct;
+pub use quad::F128Bits;
+pub use rounding::RoundingMode;
+pub use scaled::FloatToScaledInt;
+pub use slice::{mul_to_int_slice, mul_to_int_slice_scalar, SliceError};
+pub use sum::ExactProductSum;
 
 /// Float type implementing the `mul_to_int` function.
-pub trait FloatMulToInt {
+pub trait FloatMulToInt: NarrowFloat {
 	/// Integer output type.
-	type Output;
+	type Output: Integer;
+
+	/// Multiplies the two input numbers `a` and `b` *without
+	/// approximation*.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	///
+	/// ```
+	/// use fmul_to_int::FloatMulToInt;
+	///
+	/// let product = 1e10f64.mul_exact(1e10).unwrap();
+	/// assert!(product == 100_000_000_000_000_000_000i128);
+	/// ```
+	fn mul_exact(self, other: Self) -> Result<ExactProduct, Error> {
+		self.decompose().mul(other.decompose())
+	}
 
 	/// Multiplies the two input numbers `a` and `b`, and returns the
 	/// integer part of the result *without approximation*.
 	/// The fractional part is truncated.
 	///
-	/// This function returns an `Overflow` error if the integer
+	/// This function returns an [`Error::Overflow`] error if the integer
 	/// part does not fit into the [`Self::Output`] type.
 	///
-	/// # Panics
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite. No other error is returned; in particular,
+	/// [`Error::ScaleTooLarge`] only comes from decimal scaling.
+	fn mul_to_int(self, other: Self) -> Result<Self::Output, Error> {
+		self.mul_to_int_rounded(other, RoundingMode::TowardZero)
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and rounds the result
+	/// to an integer *without approximation* using the given rounding
+	/// `mode`.
 	///
-	/// This function panics if the input values are not finite
-	/// (so if at least one of them is infinite or NaN).
-	fn mul_to_int(self, other: Self) -> Result<Self::Output, Overflow>;
-}
+	/// The rounding is computed from the exact product, so the result is
+	/// never rounded twice.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded result
+	/// does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	fn mul_to_int_rounded(self, other: Self, mode: RoundingMode) -> Result<Self::Output, Error> {
+		self.mul_exact(other)?.to_int(mode)
+	}
 
-impl FloatMulToInt for f32 {
-	type Output = i64;
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result *without approximation*, along with the
+	/// exact fractional part that [`mul_to_int`](Self::mul_to_int)
+	/// truncates.
+	///
+	/// The sum of the integer part and the fractional part is exactly
+	/// `a * b`. The fractional part has the same sign as the product.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	fn mul_to_int_with_fraction(self, other: Self) -> Result<(Self::Output, Fraction), Error> {
+		let product = self.mul_exact(other)?;
+		let integer = product.to_int(RoundingMode::TowardZero)?;
+		Ok((integer, product.fraction()))
+	}
 
-	fn mul_to_int(self, other: f32) -> Result<i64, Overflow> {
-		/// Mask for the sign bit of the `i64` integer type.
-		const SIGN_MASK: u64 = 1 << 63;
-
-		/// Decompose a `f32` value into its sign, exponent and significand.
-		#[derive(Debug)]
-		struct DecomposedF32 {
-			/// Sign bit.
-			///
-			/// False is positive, true is negative.
-			sign: bool,
-
-			/// Exponent.
-			exponent: i16,
-
-			/// Significand, with the implicit largest `1`-bit omitted in the `f32`
-			/// representation.
-			significand: u32,
-		}
-
-		impl DecomposedF32 {
-			pub fn new(value: f32) -> Self {
-				if !value.is_finite() {
-					panic!("input must be finite")
-				}
-
-				if value == 0.0 {
-					Self {
-						sign: false,
-						exponent: 0,
-						significand: 0,
-					}
-				} else {
-					let raw = value.to_bits();
-
-					Self {
-						sign: (raw >> 31) == 1,
-						exponent: ((raw >> 23) & 0xff) as i16 - 127,
-						significand: 1 << 31 | raw << 8,
-					}
-				}
-			}
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result *without approximation* as an integer of
+	/// type `T`.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into `T`, for instance if it is negative and `T`
+	/// is unsigned.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	///
+	/// ```
+	/// use fmul_to_int::FloatMulToInt;
+	///
+	/// assert_eq!(1.5f64.mul_to_int_as::<u32>(3.0).unwrap(), 4u32);
+	/// assert!((-1.5f64).mul_to_int_as::<u32>(3.0).is_err());
+	/// ```
+	fn mul_to_int_as<T: Integer>(self, other: Self) -> Result<T, Error> {
+		self.mul_exact(other)?.to_int(RoundingMode::TowardZero)
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result *without approximation* as an arbitrary
+	/// precision integer.
+	/// The fractional part is truncated.
+	///
+	/// This function never overflows. An [`Error::NotANumber`] or
+	/// [`Error::Infinite`] error is returned if the product is not finite.
+	fn mul_to_int_big(self, other: Self) -> Result<BigInt, Error> {
+		Ok(BigInt::from_product(self.mul_exact(other)?))
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result, clamped to the range of
+	/// [`Self::Output`].
+	/// The fractional part is truncated.
+	///
+	/// This function never fails. As with `as` casts, an infinite product
+	/// saturates to the minimum or maximum value, and NaN is mapped to `0`.
+	fn mul_to_int_saturating(self, other: Self) -> Self::Output {
+		let (a, b) = (self.decompose(), other.decompose());
+		match a.mul(b) {
+			Ok(product) => product.to_int_saturating(),
+			Err(Error::Infinite) if a.is_negative() ^ b.is_negative() => Self::Output::MIN,
+			Err(Error::Infinite) => Self::Output::MAX,
+			Err(_) => Self::Output::ZERO,
+		}
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result modulo `2^N`, where `N` is the number of
+	/// bits of [`Self::Output`].
+	/// The fractional part is truncated.
+	///
+	/// This function never fails. NaN and infinite products are mapped to
+	/// `0`.
+	fn mul_to_int_wrapping(self, other: Self) -> Self::Output {
+		match self.mul_exact(other) {
+			Ok(product) => product.to_int_wrapping(),
+			Err(_) => Self::Output::ZERO,
 		}
+	}
 
-		let a = DecomposedF32::new(self);
-		let b = DecomposedF32::new(other);
-
-		let exponent = a.exponent + b.exponent;
-		if exponent > 62 {
-			// The absolute value is simply too big.
-			Err(Overflow)
-		} else if exponent < 0 {
-			// Integer part is 0.
-			Ok(0)
-		} else {
-			let significand = a.significand as u64 * b.significand as u64;
-			let shift = 62 - exponent as u8;
-			let unsigned = significand >> shift;
-
-			if unsigned & SIGN_MASK != 0 {
-				// No room for the sign.
-				return Err(Overflow);
-			} else if a.sign ^ b.sign {
-				Ok(-(unsigned as i64))
-			} else {
-				Ok(unsigned as i64)
-			}
+	/// Compares the exact product of the two input numbers `a` and `b` with
+	/// the integer `n`.
+	///
+	/// The product is never rounded and this function never overflows,
+	/// even if the product does not fit into [`Self::Output`]. Infinite
+	/// products are greater or lower than every integer. Returns `None` if
+	/// the product is NaN.
+	///
+	/// ```
+	/// use core::cmp::Ordering;
+	/// use fmul_to_int::FloatMulToInt;
+	///
+	/// let (rate, elapsed, quota) = (0.1f64, 30.0f64, 3i64);
+	/// assert_eq!(rate.cmp_product_to_int(elapsed, quota), Some(Ordering::Greater));
+	/// assert_eq!(f64::MAX.cmp_product_to_int(2.0, i128::MAX), Some(Ordering::Greater));
+	/// ```
+	fn cmp_product_to_int(self, other: Self, n: impl Into<i128>) -> Option<Ordering> {
+		let (a, b) = (self.decompose(), other.decompose());
+		match a.mul(b) {
+			Ok(product) => Some(product.cmp_int(n.into())),
+			Err(Error::Infinite) if a.is_negative() ^ b.is_negative() => Some(Ordering::Less),
+			Err(Error::Infinite) => Some(Ordering::Greater),
+			Err(_) => None,
 		}
 	}
 }
 
+impl FloatMulToInt for f32 {
+	type Output = i64;
+}
+
 impl FloatMulToInt for f64 {
 	type Output = i128;
+}
 
-	fn mul_to_int(self, other: f64) -> Result<i128, Overflow> {
-		/// Mask for the sign bit of the `i128` integer type.
-		const SIGN_MASK: u128 = 1 << 127;
-
-		/// Decompose a `f64` value into its sign, exponent and significand.
-		#[derive(Debug)]
-		struct DecomposedF64 {
-			/// Sign bit.
-			///
-			/// False is positive, true is negative.
-			sign: bool,
-
-			/// Exponent.
-			exponent: i16,
-
-			/// Significand, with the implicit largest `1`-bit omitted in the `f64`
-			/// representation.
-			significand: u64,
-		}
-
-		impl DecomposedF64 {
-			pub fn new(value: f64) -> Self {
-				if !value.is_finite() {
-					panic!("input must be finite")
-				}
-
-				if value == 0.0 {
-					Self {
-						sign: false,
-						exponent: 0,
-						significand: 0,
-					}
-				} else {
-					let raw = value.to_bits();
-
-					Self {
-						sign: (raw >> 63) == 1,
-						exponent: ((raw >> 52) & 0x7FF) as i16 - 1023,
-						significand: 1 << 63 | raw << 11,
-					}
-				}
-			}
-		}
-
-		let a = DecomposedF64::new(self);
-		let b = DecomposedF64::new(other);
-
-		let exponent = a.exponent + b.exponent;
-		if exponent > 126 {
-			// The absolute value is simply too big.
-			Err(Overflow)
-		} else if exponent < 0 {
-			// Integer part is 0.
-			Ok(0)
-		} else {
-			let significand = a.significand as u128 * b.significand as u128;
-			let shift = 126 - exponent as u8;
-			let unsigned = significand >> shift;
-
-			if unsigned & SIGN_MASK != 0 {
-				// No room for the sign.
-				return Err(Overflow);
-			} else if a.sign ^ b.sign {
-				Ok(-(unsigned as i128))
-			} else {
-				Ok(unsigned as i128)
-			}
-		}
-	}
+/// Converts an `f32` into the `f64` of the same value.
+///
+/// The conversion is exact: the 24-bit significand of an `f32` fits in the
+/// 53 bits of an `f64`, and its exponent range, subnormals included, is
+/// within the normal range of `f64`. Operations on `f32` can therefore be
+/// computed on the widened values without changing their result.
+pub(crate) const fn widen(value: f32) -> f64 {
+	value as f64
 }
 
-#[derive(Debug)]
-pub struct Overflow;
+/// Error returned when the result cannot be represented by the output
+/// integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+	/// The result is finite, but does not fit into the output type.
+	Overflow {
+		/// Sign of the result.
+		///
+		/// False is positive, true is negative.
+		negative: bool,
+	},
+
+	/// The result is not a number.
+	NotANumber,
+
+	/// The result is infinite.
+	Infinite,
+
+	/// The requested decimal scale is larger than `10^38`, the largest
+	/// power of ten fitting in a `u128`.
+	///
+	/// This error is only returned by [`FloatToScaledInt`].
+	ScaleTooLarge,
+}
 
-impl core::fmt::Display for Overflow {
+impl core::fmt::Display for Error {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-		write!(f, "integer overflow")
+		match self {
+			Self::Overflow { negative: false } => write!(f, "integer overflow"),
+			Self::Overflow { negative: true } => write!(f, "negative integer overflow"),
+			Self::NotANumber => write!(f, "not a number"),
+			Self::Infinite => write!(f, "infinite value"),
+			Self::ScaleTooLarge => write!(f, "decimal scale too large"),
+		}
 	}
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for Overflow {}
+impl std::error::Error for Error {}
 
 #[cfg(test)]
 mod tests {
-	use crate::FloatMulToInt;
+	use crate::{test_utils::check_rounding, Error, FloatMulToInt, RoundingMode};
 
 	#[test]
 	fn test_f32() {
@@ -247,4 +358,304 @@ mod tests {
 			assert_eq!(a.mul_to_int(b).unwrap(), c);
 		}
 	}
+
+	#[test]
+	fn test_rounded() {
+		let vectors = [
+			(2.5f64, 1.0f64, [2i128, 2, 3, 2, 3]),
+			(3.5, 1.0, [3, 3, 4, 4, 4]),
+			(-2.5, 1.0, [-2, -3, -2, -2, -3]),
+			(-3.5, 1.0, [-3, -4, -3, -4, -4]),
+			(0.75, 3.0, [2, 2, 3, 2, 2]),
+			(-0.75, 3.0, [-2, -3, -2, -2, -2]),
+			(0.1, 0.1, [0, 0, 1, 0, 0]),
+			(-0.1, 0.1, [0, -1, 0, 0, 0]),
+			(0.5, 1.0, [0, 0, 1, 0, 1]),
+			(0.5, 0.5, [0, 0, 1, 0, 0]),
+			(1e-30, 1e-30, [0, 0, 1, 0, 0]),
+			(-1e-30, 1e-30, [0, -1, 0, 0, 0]),
+			(4.0, 4.0, [16, 16, 16, 16, 16]),
+			(0.0, -3.0, [0, 0, 0, 0, 0]),
+		];
+
+		for (a, b, expected) in vectors {
+			check_rounding(expected, |mode| a.mul_to_int_rounded(b, mode).unwrap());
+			check_rounding(expected.map(|c| c as i64), |mode| {
+				(a as f32).mul_to_int_rounded(b as f32, mode).unwrap()
+			});
+		}
+	}
+
+	#[test]
+	fn test_with_fraction() {
+		let vectors = [
+			(2.5f64, 1.0f64, 2i128, 0.5f64),
+			(-0.75, 3.0, -2, -0.25),
+			(0.1, 10.0, 1, f64::EPSILON / 4.0), // `0.1` is not a `f64`.
+			(0.0, -3.0, 0, 0.0),
+			(2f64.powi(-1000), 2f64.powi(-50), 0, f64::from_bits(1 << 24)), // `2^-1050`, subnormal.
+			(123.0, 4.0, 492, 0.0),
+		];
+
+		for (a, b, c, d) in vectors {
+			let (integer, fraction) = a.mul_to_int_with_fraction(b).unwrap();
+			assert_eq!(integer, c);
+			assert_eq!(fraction.to_f64(), Some(d));
+		}
+
+		for (a, b) in [(0.1f32, 0.2f32), (-1234.5678, 0.001), (3.0, -7.1)] {
+			let (integer, fraction) = a.mul_to_int_with_fraction(b).unwrap();
+			assert_eq!(
+				integer as f64 + fraction.to_f64().unwrap(),
+				a as f64 * b as f64
+			);
+		}
+
+		let x = 1.0 + f64::EPSILON;
+		let (integer, fraction) = x.mul_to_int_with_fraction(x).unwrap();
+		assert_eq!(integer, 1);
+		assert_eq!(fraction.numerator(), (1 << 53) + 1);
+		assert_eq!(fraction.shift(), 104);
+		assert_eq!(fraction.to_f64(), None);
+	}
+
+	#[test]
+	fn test_subnormal() {
+		let vectors = [
+			(f64::MIN_POSITIVE / 2.0, 2f64.powi(1023), 1i128),
+			(f64::MIN_POSITIVE / 2.0, f64::MAX, 1),
+			(f64::MIN_POSITIVE * 3.0, 2f64.powi(1000) * 1.5, 0),
+			(f64::from_bits(0x000F_FFFF_FFFF_FFFF), f64::MAX, 3),
+			(f64::MIN_POSITIVE, 2f64.powi(1023), 2),
+			(-f64::MIN_POSITIVE / 4.0, 2f64.powi(1023), 0),
+			(f64::from_bits(1), 2f64.powi(1023), 0),
+			(f64::from_bits(3), f64::MAX, 0),
+			(f64::from_bits(1 << 51), f64::MAX, 1),
+			(f64::from_bits(1), f64::from_bits(1), 0),
+		];
+
+		for (a, b, c) in vectors {
+			assert_eq!(a.mul_to_int(b).unwrap(), c);
+			assert_eq!(b.mul_to_int(a).unwrap(), c);
+		}
+
+		// Every `f64` lower than `2^-1021` is `bits * 2^-1074`.
+		let boundary = f64::MIN_POSITIVE.to_bits();
+		for bits in (0..=4).chain(boundary - 4..=boundary + 4) {
+			let a = f64::from_bits(bits);
+			for (b, significand, exponent) in [
+				(2f64.powi(1000), 1, 1000),
+				(2f64.powi(1023), 1, 1023),
+				(f64::MAX, (1 << 53) - 1, 971),
+			] {
+				let c = (bits as i128 * significand) >> (1074 - exponent);
+				assert_eq!(a.mul_to_int(b).unwrap(), c);
+				assert_eq!((-a).mul_to_int(b).unwrap(), -c);
+				assert_eq!(a.mul_to_int(-b).unwrap(), -c);
+			}
+
+			let ceil = if bits == 0 { 0 } else { 1 };
+			assert_eq!(a.mul_to_int_rounded(1.0, RoundingMode::Ceil).unwrap(), ceil);
+			assert_eq!(a.mul_to_int_rounded(a, RoundingMode::Ceil).unwrap(), ceil);
+		}
+
+		// Every `f32` lower than `2^-125` is `bits * 2^-149`.
+		let boundary = f32::MIN_POSITIVE.to_bits();
+		for bits in (0..=4).chain(boundary - 4..=boundary + 4) {
+			let a = f32::from_bits(bits);
+			for (b, significand, exponent) in [
+				(2f32.powi(100), 1, 100),
+				(2f32.powi(127), 1, 127),
+				(f32::MAX, (1 << 24) - 1, 104),
+			] {
+				let c = (bits as i64 * significand) >> (149 - exponent);
+				assert_eq!(a.mul_to_int(b).unwrap(), c);
+				assert_eq!((-a).mul_to_int(b).unwrap(), -c);
+				assert_eq!(a.mul_to_int(-b).unwrap(), -c);
+			}
+
+			let ceil = if bits == 0 { 0 } else { 1 };
+			assert_eq!(a.mul_to_int_rounded(1.0, RoundingMode::Ceil).unwrap(), ceil);
+			assert_eq!(a.mul_to_int_rounded(a, RoundingMode::Ceil).unwrap(), ceil);
+		}
+	}
+
+	#[test]
+	fn test_min() {
+		assert_eq!((-2.0f32).mul_to_int(2f32.powi(62)).unwrap(), i64::MIN);
+		assert_eq!((-1.0f32).mul_to_int(2f32.powi(63)).unwrap(), i64::MIN);
+		assert_eq!(
+			2f32.powi(31).mul_to_int(-(2f32.powi(32))).unwrap(),
+			i64::MIN
+		);
+		assert!(2.0f32.mul_to_int(2f32.powi(62)).is_err());
+		assert!((-2.0f32).mul_to_int(2f32.powi(63)).is_err());
+		assert!((-1.5f32).mul_to_int(2f32.powi(63)).is_err());
+		assert_eq!(
+			(-1.0f32).mul_to_int(2f32.powi(63) - 2f32.powi(39)).unwrap(),
+			i64::MIN + (1 << 39)
+		);
+
+		assert_eq!((-2.0f64).mul_to_int(2f64.powi(126)).unwrap(), i128::MIN);
+		assert_eq!((-1.0f64).mul_to_int(2f64.powi(127)).unwrap(), i128::MIN);
+		assert_eq!(
+			2f64.powi(63).mul_to_int(-(2f64.powi(64))).unwrap(),
+			i128::MIN
+		);
+		assert!(2.0f64.mul_to_int(2f64.powi(126)).is_err());
+		assert!((-2.0f64).mul_to_int(2f64.powi(127)).is_err());
+		assert!((-1.5f64).mul_to_int(2f64.powi(127)).is_err());
+		assert_eq!(
+			(-1.0f64)
+				.mul_to_int(2f64.powi(127) - 2f64.powi(74))
+				.unwrap(),
+			i128::MIN + (1 << 74)
+		);
+	}
+
+	#[test]
+	fn test_errors() {
+		let vectors = [
+			(f64::NAN, 1.0f64, Error::NotANumber),
+			(0.0, f64::NAN, Error::NotANumber),
+			(f64::INFINITY, 0.0, Error::NotANumber),
+			(-0.0, f64::NEG_INFINITY, Error::NotANumber),
+			(f64::INFINITY, f64::NAN, Error::NotANumber),
+			(f64::INFINITY, 2.0, Error::Infinite),
+			(-1e-30, f64::INFINITY, Error::Infinite),
+			(f64::NEG_INFINITY, f64::NEG_INFINITY, Error::Infinite),
+			(1e30, 1e30, Error::Overflow { negative: false }),
+			(-1e30, 1e30, Error::Overflow { negative: true }),
+		];
+
+		for (a, b, e) in vectors {
+			assert_eq!(a.mul_to_int(b), Err(e));
+			assert_eq!((a as f32).mul_to_int(b as f32), Err(e));
+		}
+
+		assert_eq!(
+			f64::MAX.mul_to_int(-f64::MAX),
+			Err(Error::Overflow { negative: true })
+		);
+		assert_eq!(
+			1e10f32.mul_to_int(1e10),
+			Err(Error::Overflow { negative: false })
+		);
+	}
+
+	#[test]
+	fn test_cmp_product_to_int() {
+		use core::cmp::Ordering::*;
+
+		let vectors = [
+			(0.1f64, 30.0f64, 3i128, Greater),
+			(0.1, 3.0, 0, Greater),
+			(0.5, 6.0, 3, Equal),
+			(-0.5, 6.0, -3, Equal),
+			(-0.5, 6.0, -2, Less),
+			(0.0, -1.0, 0, Equal),
+			(f64::from_bits(1), -1.0, 0, Less),
+			(2f64.powi(64), 2f64.powi(63), i128::MAX, Greater),
+			(-(2f64.powi(64)), 2f64.powi(63), i128::MIN, Equal),
+			(f64::MAX, f64::MAX, i128::MAX, Greater),
+			(f64::MAX, -f64::MAX, i128::MIN, Less),
+			(f64::INFINITY, -1.0, i128::MIN, Less),
+			(f64::NEG_INFINITY, -1.0, i128::MAX, Greater),
+		];
+
+		for (a, b, n, ordering) in vectors {
+			assert_eq!(a.cmp_product_to_int(b, n), Some(ordering));
+		}
+
+		// `0.3` is slightly above three tenths in `f32`, and below in `f64`.
+		assert_eq!(0.3f32.cmp_product_to_int(10.0, 3), Some(Greater));
+		assert_eq!(0.3f64.cmp_product_to_int(10.0, 3i64), Some(Less));
+		assert_eq!(3e9f32.cmp_product_to_int(4e9, i64::MAX), Some(Greater));
+		assert_eq!(f64::NAN.cmp_product_to_int(1.0, 0), None);
+		assert_eq!(f64::INFINITY.cmp_product_to_int(0.0, 0), None);
+	}
+
+	#[test]
+	fn test_as() {
+		assert_eq!(20.0f64.mul_to_int_as::<u8>(12.75).unwrap(), 255u8);
+		assert_eq!(
+			20.0f64.mul_to_int_as::<u8>(12.8),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!((-0.5f64).mul_to_int_as::<u8>(1.5).unwrap(), 0u8);
+		assert_eq!(
+			(-1.0f64).mul_to_int_as::<u8>(1.0),
+			Err(Error::Overflow { negative: true })
+		);
+		assert_eq!((-16.0f32).mul_to_int_as::<i8>(8.0).unwrap(), i8::MIN);
+		assert!(16.0f32.mul_to_int_as::<i8>(8.0).is_err());
+		assert_eq!(
+			2f32.powi(100).mul_to_int_as::<u128>(2f32.powi(27)).unwrap(),
+			1u128 << 127
+		);
+		assert_eq!(f64::MAX.sqrt().mul_to_int_as::<u128>(0.0).unwrap(), 0u128);
+		assert_eq!(
+			2f64.powi(64)
+				.mul_to_int_as::<u128>(2f64.powi(64) - 2048.0)
+				.unwrap(),
+			u128::MAX - (1 << 75) + 1
+		);
+		assert!(2f64.powi(64).mul_to_int_as::<u128>(2f64.powi(64)).is_err());
+		assert_eq!(1e9f64.mul_to_int_as::<i64>(9.2).unwrap(), 9_199_999_999i64);
+		assert_eq!(
+			1e9f64.mul_to_int_as::<usize>(2.0).unwrap(),
+			2_000_000_000usize
+		);
+	}
+
+	#[test]
+	fn test_saturating() {
+		let vectors = [
+			(2.5f64, -3.0f64, -7i128),
+			(1e300, 1e300, i128::MAX),
+			(-1e300, 1e300, i128::MIN),
+			(-2.0, 2f64.powi(126), i128::MIN),
+			(f64::INFINITY, 2.0, i128::MAX),
+			(f64::INFINITY, -2.0, i128::MIN),
+			(f64::NEG_INFINITY, f64::NEG_INFINITY, i128::MAX),
+			(f64::INFINITY, 0.0, 0),
+			(f64::NAN, 1.0, 0),
+		];
+
+		for (a, b, c) in vectors {
+			assert_eq!(a.mul_to_int_saturating(b), c);
+		}
+
+		assert_eq!(1e30f32.mul_to_int_saturating(-1e30), i64::MIN);
+		assert_eq!(1e30f32.mul_to_int_saturating(1e30), i64::MAX);
+		assert_eq!(f32::NAN.mul_to_int_saturating(1e30), 0);
+	}
+
+	#[test]
+	fn test_wrapping() {
+		let vectors = [
+			(2.5f64, -3.0f64, -7i128),
+			(2f64.powi(127), 1.0, i128::MIN),
+			(2f64.powi(127), -1.0, i128::MIN),
+			(2f64.powi(127), 2.0, 0),
+			(3.0, 2f64.powi(127), i128::MIN),
+			(-3.0, 2f64.powi(126), 1 << 126),
+			(
+				1.5 + f64::EPSILON,
+				2f64.powi(127),
+				i128::MIN / 2 + (1 << 75),
+			),
+			(f64::MAX, f64::MAX, 0),
+			(f64::INFINITY, 2.0, 0),
+			(f64::NAN, 1.0, 0),
+		];
+
+		for (a, b, c) in vectors {
+			assert_eq!(a.mul_to_int_wrapping(b), c);
+		}
+
+		assert_eq!(3.0f32.mul_to_int_wrapping(2f32.powi(63)), i64::MIN);
+		assert_eq!((-5.0f32).mul_to_int_wrapping(2f32.powi(62)), -(1 << 62));
+		assert_eq!(f32::NEG_INFINITY.mul_to_int_wrapping(1.0), 0);
+	}
 }
@@ -0,0 +1,96 @@
+use crate::{decomposed::NarrowFloat, rounding::RoundingMode, Error, Integer};
+
+/// Float type implementing the `mul_mixed_to_int` function for a
+/// right-hand operand of the different float type `Rhs`.
+pub trait FloatMulMixedToInt<Rhs: NarrowFloat>: NarrowFloat {
+	/// Integer output type.
+	type Output: Integer;
+
+	/// Multiplies the two input numbers `a` and `b` of different float
+	/// types, and returns the integer part of the result *without
+	/// approximation*.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	///
+	/// ```
+	/// use fmul_to_int::FloatMulMixedToInt;
+	///
+	/// let reading = 1234.5f32;
+	/// let factor = 0.001f64;
+	/// assert_eq!(reading.mul_mixed_to_int(factor).unwrap(), 1i128);
+	/// assert_eq!(factor.mul_mixed_to_int(reading).unwrap(), 1i128);
+	/// ```
+	fn mul_mixed_to_int(self, other: Rhs) -> Result<Self::Output, Error> {
+		self.mul_mixed_to_int_rounded(other, RoundingMode::TowardZero)
+	}
+
+	/// Multiplies the two input numbers `a` and `b` of different float
+	/// types, and rounds the result to an integer *without approximation*
+	/// using the given rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	fn mul_mixed_to_int_rounded(
+		self,
+		other: Rhs,
+		mode: RoundingMode,
+	) -> Result<Self::Output, Error> {
+		self.decompose().mul(other.decompose())?.to_int(mode)
+	}
+}
+
+impl FloatMulMixedToInt<f64> for f32 {
+	type Output = i128;
+}
+
+impl FloatMulMixedToInt<f32> for f64 {
+	type Output = i128;
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Error, FloatMulMixedToInt, FloatMulToInt, RoundingMode};
+
+	#[test]
+	fn test_mixed() {
+		let vectors = [
+			(0.1f32, 10.0f64, 1i128),
+			(-1234.5, 0.001, -1),
+			(16777216.0, 1e10, 167_772_160_000_000_000),
+			(f32::from_bits(1), 2f64.powi(149), 1),
+			(3.0, -(2f64.powi(125)), -(3 << 125)),
+		];
+
+		for (a, b, c) in vectors {
+			assert_eq!(a.mul_mixed_to_int(b), (a as f64).mul_to_int(b));
+			assert_eq!(a.mul_mixed_to_int(b).unwrap(), c);
+			assert_eq!(b.mul_mixed_to_int(a).unwrap(), c);
+		}
+
+		let (a, b) = (f32::MAX, f64::MAX);
+		assert_eq!(
+			a.mul_mixed_to_int(b),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!(
+			b.mul_mixed_to_int(a),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!(
+			f32::INFINITY.mul_mixed_to_int(0.0f64),
+			Err(Error::NotANumber)
+		);
+		assert_eq!(
+			0.1f64.mul_mixed_to_int_rounded(3.0f32, RoundingMode::Floor),
+			Ok(0)
+		);
+	}
+}
@@ -0,0 +1,188 @@
+use core::num::FpCategory;
+
+use crate::{
+	accumulator::Accumulator, int::Integer, rounding::RoundingMode, widen, Decomposed, Error,
+};
+
+/// Float type implementing the `mul_add_to_int` function.
+pub trait FloatMulAddToInt: Sized {
+	/// Integer output type.
+	type Output: Integer;
+
+	/// Computes `a * b + c`, and returns the integer part of the result
+	/// *without approximation*.
+	/// The product is not rounded before the addition, and the fractional
+	/// part of the result is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the result is not finite.
+	fn mul_add_to_int(self, b: Self, c: Self) -> Result<Self::Output, Error> {
+		self.mul_add_to_int_rounded(b, c, RoundingMode::TowardZero)
+	}
+
+	/// Computes `a * b + c`, and rounds the result to an integer *without
+	/// approximation* using the given rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the result is not finite.
+	///
+	/// ```
+	/// use fmul_to_int::{FloatMulAddToInt, RoundingMode};
+	///
+	/// // `0.1 * 3.0` is slightly above `0.3` in `f64`, so this is not zero.
+	/// assert_eq!(0.1f64.mul_add_to_int_rounded(3.0, -0.3, RoundingMode::Ceil).unwrap(), 1i128);
+	/// ```
+	fn mul_add_to_int_rounded(
+		self,
+		b: Self,
+		c: Self,
+		mode: RoundingMode,
+	) -> Result<Self::Output, Error>;
+}
+
+impl FloatMulAddToInt for f32 {
+	type Output = i64;
+
+	fn mul_add_to_int_rounded(self, b: f32, c: f32, mode: RoundingMode) -> Result<i64, Error> {
+		mul_add(widen(self), widen(b), widen(c))?.to_int(mode)
+	}
+}
+
+impl FloatMulAddToInt for f64 {
+	type Output = i128;
+
+	fn mul_add_to_int_rounded(self, b: f64, c: f64, mode: RoundingMode) -> Result<i128, Error> {
+		mul_add(self, b, c)?.to_int(mode)
+	}
+}
+
+/// Computes `a * b + c` exactly.
+fn mul_add(a: f64, b: f64, c: f64) -> Result<Accumulator, Error> {
+	let (a, b, c) = (
+		Decomposed::<f64>::new(a),
+		Decomposed::<f64>::new(b),
+		Decomposed::<f64>::new(c),
+	);
+
+	match a.mul(b) {
+		Ok(product) => {
+			if c.category() == FpCategory::Nan {
+				Err(Error::NotANumber)
+			} else if c.category() == FpCategory::Infinite {
+				Err(Error::Infinite)
+			} else {
+				let mut result = Accumulator::new();
+				result.add(product);
+				result.add(c.to_product());
+				Ok(result)
+			}
+		}
+		Err(Error::Infinite) => {
+			let negative = a.is_negative() ^ b.is_negative();
+			if c.category() == FpCategory::Nan
+				|| (c.category() == FpCategory::Infinite && c.is_negative() != negative)
+			{
+				// Opposite infinities cancel into NaN.
+				Err(Error::NotANumber)
+			} else {
+				Err(Error::Infinite)
+			}
+		}
+		Err(e) => Err(e),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{test_utils::check_rounding, Error, FloatMulAddToInt, RoundingMode};
+
+	#[test]
+	fn test_f64() {
+		let vectors = [
+			(2.0f64, 3.0f64, 1.0f64, 7i128),
+			(2.0, 3.0, -7.5, -1),
+			(0.1, 3.0, -0.3, 0),
+			(0.0, 3.0, -0.3, 0),
+			(1e10, 1e10, -1e20, 0),
+			(1.5, 2f64.powi(126), -(2f64.powi(126)), 1 << 125),
+			(-2.0, 2f64.powi(126), 0.5, i128::MIN + 1),
+			(-2.0, 2f64.powi(126), -0.5, i128::MIN),
+			(2f64.powi(600), 2f64.powi(400), -(2f64.powi(1000)), 0),
+			(2f64.powi(600), 2f64.powi(-600), f64::MIN_POSITIVE, 1),
+			(2f64.powi(600), 2f64.powi(-600), -f64::MIN_POSITIVE, 0),
+			(f64::MAX, 1.0, -f64::MAX, 0),
+			(f64::from_bits(1), f64::from_bits(1), 5.0, 5),
+			(-(f64::from_bits(1)), f64::from_bits(1), 5.0, 4),
+			(1e15, 1e15, -1e30, -19_884_624_838_656), // `1e30` is not a `f64`.
+		];
+
+		for (a, b, c, d) in vectors {
+			assert_eq!(a.mul_add_to_int(b, c).unwrap(), d);
+		}
+	}
+
+	#[test]
+	fn test_rounded() {
+		use RoundingMode::*;
+
+		let vectors = [
+			(2.0f64, 3.0f64, -3.5f64, [2i128, 2, 3, 2, 3]),
+			(2.0, 3.0, -8.5, [-2, -3, -2, -2, -3]),
+			(0.1, 3.0, -0.3, [0, 0, 1, 0, 0]),
+			(-0.1, 3.0, 0.3, [0, -1, 0, 0, 0]),
+			(1.0, 1.0, -f64::MIN_POSITIVE, [0, 0, 1, 1, 1]),
+			(-1.0, 1.0, f64::MIN_POSITIVE, [0, -1, 0, -1, -1]),
+			(0.5, 1.0, -f64::from_bits(1), [0, 0, 1, 0, 0]),
+		];
+
+		for (a, b, c, expected) in vectors {
+			check_rounding(expected, |mode| {
+				a.mul_add_to_int_rounded(b, c, mode).unwrap()
+			});
+		}
+
+		// `0.1 * 3.0` is slightly below `0.3` in `f32`.
+		assert_eq!(
+			0.1f32.mul_add_to_int_rounded(3.0, -0.3, Ceil).unwrap(),
+			0i64
+		);
+		assert_eq!(
+			0.1f32.mul_add_to_int_rounded(3.0, -0.3, Floor).unwrap(),
+			-1i64
+		);
+	}
+
+	#[test]
+	fn test_errors() {
+		let vectors = [
+			(f64::NAN, 1.0f64, 1.0f64, Error::NotANumber),
+			(1.0, 1.0, f64::NAN, Error::NotANumber),
+			(f64::INFINITY, 0.0, 1.0, Error::NotANumber),
+			(f64::INFINITY, 1.0, f64::NEG_INFINITY, Error::NotANumber),
+			(f64::INFINITY, -1.0, f64::NEG_INFINITY, Error::Infinite),
+			(1.0, 1.0, f64::INFINITY, Error::Infinite),
+			(
+				f64::MAX,
+				2.0,
+				-f64::MAX,
+				Error::Overflow { negative: false },
+			),
+			(f64::MAX, -1.0, 1.0, Error::Overflow { negative: true }),
+		];
+
+		for (a, b, c, e) in vectors {
+			assert_eq!(a.mul_add_to_int(b, c), Err(e));
+		}
+
+		assert_eq!(
+			3e9f32.mul_add_to_int(4e9, -1.0),
+			Err(Error::Overflow { negative: false })
+		);
+	}
+}
@@ -0,0 +1,237 @@
+use crate::{
+	accumulator::Accumulator, int::Integer, product::ExactProduct, rounding::RoundingMode, widen,
+	Decomposed, Error,
+};
+
+/// Float type implementing the `mul_int_to_int` function for the integer
+/// multiplier type `I`.
+pub trait FloatMulIntToInt<I>: Sized {
+	/// Integer output type.
+	type Output: Integer;
+
+	/// Multiplies the float `a` by the integer `n`, and returns the integer
+	/// part of the result *without approximation*.
+	/// The fractional part is truncated.
+	///
+	/// The multiplier is never converted into a float, so it does not need
+	/// to be exactly representable as one.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	///
+	/// ```
+	/// use fmul_to_int::FloatMulIntToInt;
+	///
+	/// assert_eq!(1.5f64.mul_int_to_int(1_000_000_000u64).unwrap(), 1_500_000_000i128);
+	/// assert_eq!(1.0f64.mul_int_to_int(u64::MAX).unwrap(), u64::MAX as i128);
+	///
+	/// // Unsuffixed literals are `i32` multipliers.
+	/// let secs = 2.5f64;
+	/// assert_eq!(secs.mul_int_to_int(1_000_000_000).unwrap(), 2_500_000_000i128);
+	/// ```
+	fn mul_int_to_int(self, n: I) -> Result<Self::Output, Error> {
+		self.mul_int_to_int_rounded(n, RoundingMode::TowardZero)
+	}
+
+	/// Multiplies the float `a` by the integer `n`, and rounds the result
+	/// to an integer *without approximation* using the given rounding
+	/// `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	fn mul_int_to_int_rounded(self, n: I, mode: RoundingMode) -> Result<Self::Output, Error>;
+}
+
+macro_rules! mul_int_impls {
+	($kind:ident: $($ty:ident),*) => {
+		$(
+			impl FloatMulIntToInt<$ty> for f32 {
+				type Output = i64;
+
+				fn mul_int_to_int_rounded(self, n: $ty, mode: RoundingMode) -> Result<i64, Error> {
+					let (negative, magnitude) = int_magnitude!($kind, n);
+					mul_int(widen(self), negative, magnitude, mode)
+				}
+			}
+
+			impl FloatMulIntToInt<$ty> for f64 {
+				type Output = i128;
+
+				fn mul_int_to_int_rounded(self, n: $ty, mode: RoundingMode) -> Result<i128, Error> {
+					let (negative, magnitude) = int_magnitude!($kind, n);
+					mul_int(self, negative, magnitude, mode)
+				}
+			}
+		)*
+	};
+}
+
+/// Returns the sign and absolute value of an integer.
+macro_rules! int_magnitude {
+	(signed, $n:ident) => {
+		($n < 0, $n.unsigned_abs() as u128)
+	};
+	(unsigned, $n:ident) => {
+		(false, $n as u128)
+	};
+}
+
+mul_int_impls!(signed: i8, i16, i32, i64, i128, isize);
+mul_int_impls!(unsigned: u8, u16, u32, u64, u128, usize);
+
+/// Multiplies `a` by the integer of the given sign and absolute value, and
+/// rounds the exact result to an integer of type `T`.
+fn mul_int<T: Integer>(
+	a: f64,
+	negative: bool,
+	magnitude: u128,
+	mode: RoundingMode,
+) -> Result<T, Error> {
+	if a.is_nan() {
+		return Err(Error::NotANumber);
+	} else if a.is_infinite() {
+		return if magnitude == 0 {
+			Err(Error::NotANumber)
+		} else {
+			Err(Error::Infinite)
+		};
+	}
+
+	let a = Decomposed::<f64>::new(a).to_product();
+	let product = |factor: u128, shift: i32| ExactProduct {
+		negative: a.negative ^ negative,
+		significand: a.significand * factor,
+		exponent: a.exponent + shift,
+	};
+
+	let (low, high) = (magnitude as u64 as u128, magnitude >> 64);
+	if high == 0 {
+		product(low, 0).to_int(mode)
+	} else {
+		// The product does not fit in 128 bits.
+		let mut result = Accumulator::new();
+		result.add(product(low, 0));
+		result.add(product(high, 64));
+		result.to_int(mode)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{test_utils::check_rounding, Error, FloatMulIntToInt, RoundingMode};
+
+	#[test]
+	fn test_f64() {
+		let vectors = [
+			(1.5f64, 1_000_000_000u128, 1_500_000_000i128),
+			(0.1, 1_000_000_000, 100_000_000),
+			(1.0, u64::MAX as u128, u64::MAX as i128),
+			(1.0, (1 << 53) + 1, (1 << 53) + 1),
+			(0.5, u128::MAX, (u128::MAX >> 1) as i128),
+			(-0.5, u128::MAX, -((u128::MAX >> 1) as i128)),
+			(0.25, u128::MAX, (u128::MAX >> 2) as i128),
+			(-(2f64.powi(-64)), u128::MAX, -(u64::MAX as i128)),
+			(1e-30, 10u128.pow(38), 100_000_000),
+			(0.0, u128::MAX, 0),
+		];
+
+		for (a, n, c) in vectors {
+			assert_eq!(a.mul_int_to_int(n).unwrap(), c);
+		}
+
+		assert_eq!(1.5f64.mul_int_to_int(-3i64).unwrap(), -4);
+		assert_eq!((-1.5f64).mul_int_to_int(-3i64).unwrap(), 4);
+		assert_eq!(1.0f64.mul_int_to_int(i64::MIN).unwrap(), i64::MIN as i128);
+		assert_eq!(0.5f64.mul_int_to_int(i128::MIN).unwrap(), i128::MIN / 2);
+		assert_eq!(
+			(-1.0f64).mul_int_to_int(i128::MIN),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!(
+			1.0f64.mul_int_to_int(u128::MAX),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!(1e-9f64.mul_int_to_int(u64::MAX).unwrap(), 18_446_744_073);
+	}
+
+	#[test]
+	fn test_f32() {
+		// `0.1` is not a `f32`.
+		assert_eq!(
+			0.1f32.mul_int_to_int(1_000_000_000u64).unwrap(),
+			100_000_001i64
+		);
+		assert_eq!(1.0f32.mul_int_to_int(i64::MIN).unwrap(), i64::MIN);
+		assert_eq!(
+			(-1.0f32).mul_int_to_int(i64::MIN),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!(
+			2f32.powi(-70).mul_int_to_int(u128::MAX).unwrap(),
+			(1i64 << 58) - 1
+		);
+	}
+
+	#[test]
+	fn test_small_ints() {
+		assert_eq!(3.0f64.mul_int_to_int(-7).unwrap(), -21i128);
+		assert_eq!(
+			0.1f32.mul_int_to_int(1_000_000_000).unwrap(),
+			100_000_001i64
+		);
+		assert_eq!((-2.5f64).mul_int_to_int(i8::MIN).unwrap(), 320);
+		assert_eq!(1.5f32.mul_int_to_int(u8::MAX).unwrap(), 382);
+		assert_eq!(0.5f64.mul_int_to_int(i16::MIN).unwrap(), -16384);
+		assert_eq!(1.0f64.mul_int_to_int(u32::MAX).unwrap(), u32::MAX as i128);
+		assert_eq!(
+			1.0f64.mul_int_to_int(isize::MIN).unwrap(),
+			isize::MIN as i128
+		);
+		assert_eq!(
+			(-1.0f64).mul_int_to_int(usize::MAX).unwrap(),
+			-(usize::MAX as i128)
+		);
+	}
+
+	#[test]
+	fn test_rounded() {
+		use RoundingMode::*;
+
+		let vectors = [
+			(0.5f64, 5u128, [2i128, 2, 3, 2, 3]),
+			(-0.5, 5, [-2, -3, -2, -2, -3]),
+			(0.1, 10, [1, 1, 2, 1, 1]),
+			(2f64.powi(-65), 3 << 64, [1, 1, 2, 2, 2]),
+		];
+
+		for (a, n, expected) in vectors {
+			check_rounding(expected, |mode| a.mul_int_to_int_rounded(n, mode).unwrap());
+		}
+
+		assert_eq!(
+			0.5f64.mul_int_to_int_rounded(u128::MAX, Floor),
+			Ok(i128::MAX)
+		);
+		assert_eq!(
+			0.5f64.mul_int_to_int_rounded(u128::MAX, Ceil),
+			Err(Error::Overflow { negative: false })
+		);
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_eq!(f64::NAN.mul_int_to_int(1u64), Err(Error::NotANumber));
+		assert_eq!(f64::INFINITY.mul_int_to_int(0u64), Err(Error::NotANumber));
+		assert_eq!(f64::INFINITY.mul_int_to_int(-1i64), Err(Error::Infinite));
+		assert_eq!(
+			f32::NEG_INFINITY.mul_int_to_int(1u128),
+			Err(Error::Infinite)
+		);
+	}
+}
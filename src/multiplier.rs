@@ -0,0 +1,212 @@
+use crate::{rounding::RoundingMode, Decomposed, Error, Float, FloatMulToInt};
+
+/// Prepared multiplication by a constant factor.
+///
+/// The factor is validated and decomposed once, when the multiplier is
+/// created. Every application then only decomposes its input, and returns
+/// the same result as [`mul_to_int`](FloatMulToInt::mul_to_int).
+///
+/// ```
+/// use fmul_to_int::{Multiplier, RoundingMode};
+///
+/// let nanos = Multiplier::<f64>::new(1e9).unwrap();
+/// assert_eq!(nanos.apply(1.5).unwrap(), 1_500_000_000i128);
+/// assert_eq!(nanos.apply_rounded(-1e-10, RoundingMode::Floor).unwrap(), -1);
+/// assert!(nanos.apply(nanos.max_input()).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Multiplier<F: Float> {
+	/// Factor.
+	factor: F,
+
+	/// Decomposed factor.
+	decomposed: Decomposed<F>,
+
+	/// Largest input whose product cannot overflow.
+	max_input: F,
+}
+
+impl<F: FloatMulToInt> Multiplier<F> {
+	/// Returns the factor.
+	pub fn factor(&self) -> F {
+		self.factor
+	}
+
+	/// Returns the decomposed factor.
+	pub fn decomposed(&self) -> Decomposed<F> {
+		self.decomposed
+	}
+
+	/// Returns the largest `x` such that neither `x * factor` nor
+	/// `-x * factor` overflows.
+	///
+	/// Every input `x` with `|x| <= max_input` can be applied without
+	/// error. If the factor is zero, this is the largest finite value.
+	pub fn max_input(&self) -> F {
+		self.max_input
+	}
+
+	/// Multiplies `x` by the factor, and returns the integer part of the
+	/// result *without approximation*.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the output type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// `x` is not finite.
+	pub fn apply(&self, x: F) -> Result<F::Output, Error> {
+		self.apply_rounded(x, RoundingMode::TowardZero)
+	}
+
+	/// Multiplies `x` by the factor, and rounds the result to an integer
+	/// *without approximation* using the given rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into the output type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// `x` is not finite.
+	pub fn apply_rounded(&self, x: F, mode: RoundingMode) -> Result<F::Output, Error> {
+		x.decompose().mul(self.decomposed)?.to_int(mode)
+	}
+}
+
+macro_rules! multiplier_impls {
+	($($ty:ident: $output:ident),*) => {
+		$(
+			impl Multiplier<$ty> {
+				/// Creates a new multiplier by the given factor.
+				///
+				/// An [`Error::NotANumber`] or [`Error::Infinite`] error is
+				/// returned if the factor is not finite.
+				pub fn new(factor: $ty) -> Result<Self, Error> {
+					if factor.is_nan() {
+						return Err(Error::NotANumber);
+					}
+
+					if factor.is_infinite() {
+						return Err(Error::Infinite);
+					}
+
+					let mut result = Self {
+						factor,
+						decomposed: factor.decompose(),
+						max_input: $ty::MAX,
+					};
+
+					// Products are lower than `2^(N-1)` in absolute value
+					// exactly when no input overflows.
+					let bound = 2f64.powi($output::BITS as i32 - 1);
+					let fits = |x: $ty| result.apply(x).is_ok() && result.apply(-x).is_ok();
+
+					if !fits($ty::MAX) {
+						// The quotient is within a few units in the last
+						// place of the bound.
+						let mut x = ((bound / factor.abs() as f64) as $ty).min($ty::MAX);
+						while !fits(x) {
+							x = $ty::from_bits(x.to_bits() - 1);
+						}
+
+						while x < $ty::MAX && fits($ty::from_bits(x.to_bits() + 1)) {
+							x = $ty::from_bits(x.to_bits() + 1);
+						}
+
+						result.max_input = x;
+					}
+
+					Ok(result)
+				}
+			}
+		)*
+	};
+}
+
+multiplier_impls!(f32: i64, f64: i128);
+
+#[cfg(test)]
+mod tests {
+	use crate::{Error, FloatMulToInt, Multiplier, RoundingMode};
+
+	#[test]
+	fn test_apply() {
+		let factors = [
+			1e9f64,
+			100.0,
+			1e-6,
+			-0.1,
+			0.0,
+			2f64.powi(100),
+			f64::from_bits(1),
+		];
+		let values = [
+			0.0f64,
+			-0.0,
+			0.1,
+			-1.5,
+			3.7,
+			1e20,
+			-1e30,
+			f64::MAX,
+			f64::NAN,
+		];
+
+		for factor in factors {
+			let multiplier = Multiplier::<f64>::new(factor).unwrap();
+			assert_eq!(multiplier.factor(), factor);
+			for x in values {
+				assert_eq!(multiplier.apply(x), x.mul_to_int(factor));
+				for mode in [RoundingMode::Floor, RoundingMode::NearestEven] {
+					assert_eq!(
+						multiplier.apply_rounded(x, mode),
+						x.mul_to_int_rounded(factor, mode)
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_new() {
+		assert_eq!(
+			Multiplier::<f64>::new(f64::NAN).unwrap_err(),
+			Error::NotANumber
+		);
+		assert_eq!(
+			Multiplier::<f32>::new(f32::NEG_INFINITY).unwrap_err(),
+			Error::Infinite
+		);
+	}
+
+	#[test]
+	fn test_max_input() {
+		let vectors = [
+			(1.0f64, 2f64.powi(127) * (1.0 - f64::EPSILON / 2.0)),
+			(-1.0, 2f64.powi(127) * (1.0 - f64::EPSILON / 2.0)),
+			(2.0, 2f64.powi(126) * (1.0 - f64::EPSILON / 2.0)),
+			(0.5, 2f64.powi(128) * (1.0 - f64::EPSILON / 2.0)),
+			(0.0, f64::MAX),
+			(f64::from_bits(1), f64::MAX),
+			(2f64.powi(-1000), f64::MAX),
+		];
+
+		for (factor, max) in vectors {
+			assert_eq!(Multiplier::<f64>::new(factor).unwrap().max_input(), max);
+		}
+
+		for factor in [1e9f64, 100.0, 1e-6, -0.1, 3.0, 1e300] {
+			let multiplier = Multiplier::<f64>::new(factor).unwrap();
+			let max = multiplier.max_input();
+			let next = f64::from_bits(max.to_bits() + 1);
+			assert!(multiplier.apply(max).is_ok());
+			assert!(multiplier.apply(-max).is_ok());
+			assert!(multiplier.apply(next).is_err() || multiplier.apply(-next).is_err());
+		}
+
+		let multiplier = Multiplier::<f32>::new(1e9).unwrap();
+		let max = multiplier.max_input();
+		let next = f32::from_bits(max.to_bits() + 1);
+		assert!(multiplier.apply(max).is_ok() && multiplier.apply(-max).is_ok());
+		assert!(multiplier.apply(next).is_err());
+	}
+}
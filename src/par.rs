@@ -0,0 +1,154 @@
+use rayon::prelude::*;
+
+use crate::{rounding::RoundingMode, FloatMulToInt, SliceError};
+
+/// Parallel version of [`mul_to_int_slice`](crate::mul_to_int_slice).
+///
+/// Multiplies the elements of `a` and `b` pairwise on the `rayon` thread
+/// pool, and stores the integer part of each product in `out`, *without
+/// approximation*.
+///
+/// The result does not depend on the scheduling: every element that can be
+/// converted is written, failing elements are left untouched, and the
+/// failing element with the lowest index is reported.
+///
+/// # Panics
+///
+/// Panics if the three slices do not have the same length.
+///
+/// ```
+/// use fmul_to_int::{par_mul_to_int, Error, SliceError};
+///
+/// let a = [1.5, f64::MAX, 2.0, f64::NAN];
+/// let b = [4.0, 2.0, 0.25, 1.0];
+/// let mut out = [0i128; 4];
+/// let error = par_mul_to_int(&a, &b, &mut out).unwrap_err();
+/// assert_eq!(error, SliceError { index: 1, error: Error::Overflow { negative: false } });
+/// assert_eq!(out, [6, 0, 0, 0]);
+/// ```
+pub fn par_mul_to_int<F>(a: &[F], b: &[F], out: &mut [F::Output]) -> Result<(), SliceError>
+where
+	F: FloatMulToInt + Sync,
+	F::Output: Send,
+{
+	assert_eq!(a.len(), b.len(), "input slices have different lengths");
+	assert_eq!(a.len(), out.len(), "output slice has a different length");
+
+	a.par_iter()
+		.zip(b)
+		.zip(out)
+		.enumerate()
+		.filter_map(|(index, ((a, b), out))| match a.mul_to_int(*b) {
+			Ok(value) => {
+				*out = value;
+				None
+			}
+			Err(error) => Some(SliceError { index, error }),
+		})
+		.min_by_key(|e| e.index)
+		.map_or(Ok(()), Err)
+}
+
+/// Parallel version of
+/// [`mul_to_int_slice_scalar`](crate::mul_to_int_slice_scalar).
+///
+/// Multiplies every element of `a` by the scalar `b` on the `rayon` thread
+/// pool, and stores the integer part of each product in `out`, *without
+/// approximation*. Errors are handled as in [`par_mul_to_int`].
+///
+/// # Panics
+///
+/// Panics if `a` and `out` do not have the same length.
+pub fn par_mul_to_int_scalar<F>(a: &[F], b: F, out: &mut [F::Output]) -> Result<(), SliceError>
+where
+	F: FloatMulToInt + Sync,
+	F::Output: Send,
+{
+	assert_eq!(a.len(), out.len(), "output slice has a different length");
+
+	let b = b.decompose();
+	a.par_iter()
+		.zip(out)
+		.enumerate()
+		.filter_map(|(index, (a, out))| {
+			match a
+				.decompose()
+				.mul(b)
+				.and_then(|product| product.to_int(RoundingMode::TowardZero))
+			{
+				Ok(value) => {
+					*out = value;
+					None
+				}
+				Err(error) => Some(SliceError { index, error }),
+			}
+		})
+		.min_by_key(|e| e.index)
+		.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		par_mul_to_int, par_mul_to_int_scalar, test_utils::xorshift, Error, FloatMulToInt,
+		SliceError,
+	};
+
+	/// Deterministic pseudo-random `f64` values.
+	fn values(n: usize) -> Vec<f64> {
+		xorshift(0x2545_f491_4f6c_dd1d)
+			.take(n)
+			.enumerate()
+			.map(|(i, bits)| (bits >> 11) as f64 * 2f64.powi((i % 100) as i32 - 60))
+			.collect()
+	}
+
+	#[test]
+	fn test_par() {
+		let a = values(100_000);
+		let b: Vec<f64> = a.iter().rev().copied().collect();
+		let mut out = vec![0i128; a.len()];
+		par_mul_to_int(&a, &b, &mut out).unwrap();
+		for i in 0..a.len() {
+			assert_eq!(out[i], a[i].mul_to_int(b[i]).unwrap());
+		}
+
+		let mut out = vec![0i128; a.len()];
+		par_mul_to_int_scalar(&a, -0.1, &mut out).unwrap();
+		for i in 0..a.len() {
+			assert_eq!(out[i], a[i].mul_to_int(-0.1).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_lowest_index() {
+		let mut a = values(100_000);
+		for i in [99_999, 70_001, 50_000, 123] {
+			a[i] = f64::MAX;
+		}
+		a[123] = f64::NAN;
+
+		for _ in 0..10 {
+			let mut out = vec![0i128; a.len()];
+			assert_eq!(
+				par_mul_to_int_scalar(&a, 2.0, &mut out),
+				Err(SliceError {
+					index: 123,
+					error: Error::NotANumber
+				})
+			);
+			assert_eq!(out[100], a[100].mul_to_int(2.0).unwrap());
+			assert_eq!(out[99_998], a[99_998].mul_to_int(2.0).unwrap());
+			assert_eq!(out[50_000], 0);
+
+			let b = vec![-2.0; a.len()];
+			assert_eq!(
+				par_mul_to_int(&a[1000..], &b[1000..], &mut out[1000..]),
+				Err(SliceError {
+					index: 49_000,
+					error: Error::Overflow { negative: true }
+				})
+			);
+		}
+	}
+}
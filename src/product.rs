@@ -0,0 +1,529 @@
+use core::cmp::Ordering;
+
+use crate::{
+	fraction::Fraction,
+	int::Integer,
+	rounding::{Remainder, RoundingMode},
+	Decomposed, Error,
+};
+
+/// Exact product of two floating point numbers.
+///
+/// The absolute value of the product is `significand * 2^exponent`, where
+/// the significand is a 128-bit integer. This is enough to hold the product
+/// of any two finite `f32` or `f64` values without rounding.
+///
+/// Products are compared by value, against other products, integers and
+/// floats.
+///
+/// ```
+/// use fmul_to_int::{FloatMulToInt, RoundingMode};
+///
+/// let product = 0.1f64.mul_exact(3.0).unwrap();
+/// assert!(product > 0.3); // `0.1` and `0.3` are not `f64`.
+/// assert_eq!(product.to_f64(), 0.1 * 3.0);
+/// assert_eq!(product.to_int::<i32>(RoundingMode::NearestEven).unwrap(), 0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ExactProduct {
+	/// Sign bit.
+	///
+	/// False is positive, true is negative.
+	pub(crate) negative: bool,
+
+	/// Significand.
+	pub(crate) significand: u128,
+
+	/// Binary exponent.
+	pub(crate) exponent: i32,
+}
+
+impl ExactProduct {
+	/// Zero product.
+	pub const ZERO: Self = Self {
+		negative: false,
+		significand: 0,
+		exponent: 0,
+	};
+
+	/// Creates the integer with the given sign and absolute value.
+	const fn from_magnitude(negative: bool, magnitude: u128) -> Self {
+		Self {
+			negative,
+			significand: magnitude,
+			exponent: 0,
+		}
+	}
+
+	/// Checks if the sign bit is set.
+	///
+	/// A zero product may have its sign bit set, following the IEEE 754
+	/// sign rules of the multiplication.
+	pub const fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Checks if the product is zero.
+	pub const fn is_zero(&self) -> bool {
+		self.significand == 0
+	}
+
+	/// Returns the significand of the absolute value.
+	pub const fn significand(&self) -> u128 {
+		self.significand
+	}
+
+	/// Returns the binary exponent of the absolute value.
+	pub const fn exponent(&self) -> i32 {
+		self.exponent
+	}
+
+	/// Splits the absolute value of the product into its integer part and
+	/// the position of its fractional part.
+	///
+	/// Returns `None` if the integer part does not fit in a `u128`.
+	pub(crate) const fn split(self) -> Option<(u128, Remainder)> {
+		if self.significand == 0 {
+			Some((0, Remainder::Zero))
+		} else if self.exponent >= 0 {
+			if self.exponent > self.significand.leading_zeros() as i32 {
+				None
+			} else {
+				Some((self.significand << self.exponent, Remainder::Zero))
+			}
+		} else {
+			let shift = self.exponent.unsigned_abs();
+			if shift >= 128 {
+				Some((0, Remainder::of_shifted(self.significand, shift)))
+			} else {
+				let integer = self.significand >> shift;
+				let fraction = self.significand & ((1 << shift) - 1);
+				Some((integer, Remainder::of_shifted(fraction, shift)))
+			}
+		}
+	}
+
+	/// Rounds the absolute value of the product to an integer.
+	///
+	/// Returns `None` if the result does not fit in a `u128`.
+	pub(crate) const fn round_magnitude(self, mode: RoundingMode) -> Option<u128> {
+		match self.split() {
+			Some((integer, remainder)) => mode.round(self.negative, integer, remainder),
+			None => None,
+		}
+	}
+
+	/// Returns the exact fractional part of the product, as a fraction.
+	pub fn fraction(self) -> Fraction {
+		if self.exponent >= 0 {
+			Fraction::ZERO
+		} else {
+			let shift = self.exponent.unsigned_abs();
+			let numerator = if shift >= 128 {
+				self.significand
+			} else {
+				self.significand & ((1 << shift) - 1)
+			};
+
+			Fraction::new(self.negative, numerator, shift)
+		}
+	}
+
+	/// Returns the overflow error for this product.
+	pub(crate) fn overflow(self) -> Error {
+		Error::Overflow {
+			negative: self.negative,
+		}
+	}
+
+	/// Rounds the product to an integer of type `T` using the given
+	/// rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into `T`.
+	pub fn to_int<T: Integer>(self, mode: RoundingMode) -> Result<T, Error> {
+		self.round_magnitude(mode)
+			.and_then(|magnitude| T::from_magnitude(self.negative, magnitude))
+			.ok_or(self.overflow())
+	}
+
+	/// Converts the product to an integer of type `T`, truncating the
+	/// fractional part and clamping the result to the range of `T`.
+	pub(crate) fn to_int_saturating<T: Integer>(self) -> T {
+		match self.round_magnitude(RoundingMode::TowardZero) {
+			Some(magnitude) => T::saturating_from_magnitude(self.negative, magnitude),
+			None if self.negative => T::MIN,
+			None => T::MAX,
+		}
+	}
+
+	/// Converts the product to an integer of type `T`, truncating the
+	/// fractional part and wrapping the result around the boundaries of `T`.
+	pub(crate) fn to_int_wrapping<T: Integer>(self) -> T {
+		let magnitude = if self.exponent >= 128 {
+			0
+		} else if self.exponent >= 0 {
+			self.significand << self.exponent
+		} else {
+			self.round_magnitude(RoundingMode::TowardZero).unwrap()
+		};
+
+		T::wrapping_from_magnitude(self.negative, magnitude)
+	}
+
+	/// Rounds the product to an integer product with the given rounding
+	/// `mode`.
+	fn round_to_product(self, mode: RoundingMode) -> Self {
+		if self.exponent >= 0 {
+			self
+		} else {
+			// The integer part is lower than `2^127`, rounding cannot
+			// overflow.
+			Self::from_magnitude(self.negative, self.round_magnitude(mode).unwrap())
+		}
+	}
+
+	/// Returns the largest integer lower than or equal to the product.
+	pub fn floor(self) -> Self {
+		self.round_to_product(RoundingMode::Floor)
+	}
+
+	/// Returns the smallest integer greater than or equal to the product.
+	pub fn ceil(self) -> Self {
+		self.round_to_product(RoundingMode::Ceil)
+	}
+
+	/// Returns the integer part of the product, truncating the fractional
+	/// part.
+	pub fn trunc(self) -> Self {
+		self.round_to_product(RoundingMode::TowardZero)
+	}
+
+	/// Returns the fractional part of the product, such that
+	/// `self.trunc() + self.fract()` is exactly the product.
+	pub fn fract(self) -> Self {
+		if self.exponent >= 0 {
+			Self {
+				negative: self.negative,
+				..Self::ZERO
+			}
+		} else {
+			let shift = self.exponent.unsigned_abs();
+			let significand = if shift >= 128 {
+				self.significand
+			} else {
+				self.significand & ((1 << shift) - 1)
+			};
+
+			Self {
+				significand,
+				..self
+			}
+		}
+	}
+
+	/// Converts the product to the nearest `f64`, ties to even.
+	pub fn to_f64(self) -> f64 {
+		self.to_f64_rounded(RoundingMode::NearestEven)
+	}
+
+	/// Converts the product to a `f64` using the given rounding `mode`.
+	///
+	/// Products too large to be represented are rounded to an infinity or
+	/// to the largest finite `f64`, as IEEE 754 prescribes for the rounding
+	/// mode.
+	pub fn to_f64_rounded(self, mode: RoundingMode) -> f64 {
+		let result = if self.significand == 0 {
+			0.0
+		} else {
+			let bits = 128 - self.significand.leading_zeros() as i32;
+
+			// Number of low bits that do not fit in the `f64` significand,
+			// or below the smallest subnormal value.
+			let drop = (bits - f64::MANTISSA_DIGITS as i32).max(-1074 - self.exponent);
+
+			let (significand, exponent) = if drop <= 0 {
+				(self.significand, self.exponent)
+			} else if drop >= 128 {
+				let remainder = Remainder::of_shifted(self.significand, drop as u32);
+				let significand = mode.round(self.negative, 0, remainder).unwrap();
+				(significand, self.exponent + drop)
+			} else {
+				let integer = self.significand >> drop;
+				let fraction = self.significand & ((1 << drop) - 1);
+				let remainder = Remainder::of_shifted(fraction, drop as u32);
+				let significand = mode.round(self.negative, integer, remainder).unwrap();
+				(significand, self.exponent + drop)
+			};
+
+			let top = exponent + 127 - significand.leading_zeros() as i32;
+			if significand == 0 {
+				0.0
+			} else if top > 1023 {
+				let away = match mode {
+					RoundingMode::TowardZero => false,
+					RoundingMode::Floor => self.negative,
+					RoundingMode::Ceil => !self.negative,
+					RoundingMode::NearestEven | RoundingMode::NearestAway => true,
+				};
+
+				if away {
+					f64::INFINITY
+				} else {
+					f64::MAX
+				}
+			} else {
+				if top < -1022 {
+					// Subnormal range, where the exponent is `-1074` and the
+					// significand is the encoding itself.
+					f64::from_bits((significand << (exponent + 1074)) as u64)
+				} else if exponent < -1022 {
+					// The power of two alone would be subnormal, so it is
+					// applied in two exact steps.
+					significand as f64 * pow2(exponent + 64) * pow2(-64)
+				} else {
+					// Both the significand and the power of two are exact,
+					// and so is their product.
+					significand as f64 * pow2(exponent)
+				}
+			}
+		};
+
+		if self.negative {
+			-result
+		} else {
+			result
+		}
+	}
+
+	/// Compares the absolute values of two products.
+	fn cmp_magnitude(&self, other: &Self) -> Ordering {
+		match (self.significand == 0, other.significand == 0) {
+			(true, true) => Ordering::Equal,
+			(true, false) => Ordering::Less,
+			(false, true) => Ordering::Greater,
+			(false, false) => {
+				let a_top = self.exponent - self.significand.leading_zeros() as i32;
+				let b_top = other.exponent - other.significand.leading_zeros() as i32;
+				a_top.cmp(&b_top).then_with(|| {
+					// Both values have the same number of integer bits, so
+					// aligning the exponents cannot overflow.
+					if self.exponent >= other.exponent {
+						let shift = (self.exponent - other.exponent) as u32;
+						(self.significand << shift).cmp(&other.significand)
+					} else {
+						let shift = (other.exponent - self.exponent) as u32;
+						self.significand.cmp(&(other.significand << shift))
+					}
+				})
+			}
+		}
+	}
+
+	/// Compares the product with the given integer.
+	pub fn cmp_int(&self, n: i128) -> Ordering {
+		self.cmp(&Self::from_magnitude(n < 0, n.unsigned_abs()))
+	}
+
+	/// Compares the product with the given float.
+	///
+	/// Returns `None` if the float is NaN.
+	pub fn partial_cmp_f64(&self, x: f64) -> Option<Ordering> {
+		if x.is_nan() {
+			None
+		} else if x.is_infinite() {
+			Some(if x > 0.0 {
+				Ordering::Less
+			} else {
+				Ordering::Greater
+			})
+		} else {
+			Some(self.cmp(&Decomposed::<f64>::new(x).to_product()))
+		}
+	}
+}
+
+/// Returns `2^exponent`, which must be a normal `f64`.
+fn pow2(exponent: i32) -> f64 {
+	f64::from_bits(((exponent + 1023) as u64) << 52)
+}
+
+impl PartialEq for ExactProduct {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for ExactProduct {}
+
+impl PartialOrd for ExactProduct {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for ExactProduct {
+	fn cmp(&self, other: &Self) -> Ordering {
+		let a_negative = self.negative && self.significand != 0;
+		let b_negative = other.negative && other.significand != 0;
+		match (a_negative, b_negative) {
+			(false, false) => self.cmp_magnitude(other),
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+			(true, true) => other.cmp_magnitude(self),
+		}
+	}
+}
+
+impl PartialEq<i128> for ExactProduct {
+	fn eq(&self, other: &i128) -> bool {
+		self.cmp_int(*other) == Ordering::Equal
+	}
+}
+
+impl PartialOrd<i128> for ExactProduct {
+	fn partial_cmp(&self, other: &i128) -> Option<Ordering> {
+		Some(self.cmp_int(*other))
+	}
+}
+
+impl PartialEq<f64> for ExactProduct {
+	fn eq(&self, other: &f64) -> bool {
+		self.partial_cmp_f64(*other) == Some(Ordering::Equal)
+	}
+}
+
+impl PartialOrd<f64> for ExactProduct {
+	fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+		self.partial_cmp_f64(*other)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cmp::Ordering;
+
+	use crate::{test_utils::xorshift, FloatMulToInt, RoundingMode};
+
+	/// Deterministic pseudo-random `f64` values, covering the whole range.
+	fn values() -> impl Iterator<Item = f64> {
+		xorshift(0x2545_f491_4f6c_dd1d)
+			.take(2000)
+			.map(f64::from_bits)
+	}
+
+	#[test]
+	fn test_to_f64() {
+		let values: Vec<f64> = values()
+			.filter(|x| x.is_finite())
+			.chain([
+				0.0,
+				-0.0,
+				1.0,
+				0.1,
+				f64::MAX,
+				f64::MIN_POSITIVE,
+				f64::from_bits(1),
+				f64::from_bits(0x000f_ffff_ffff_ffff),
+			])
+			.collect();
+
+		for (i, a) in values.iter().enumerate() {
+			for b in values.iter().skip(i % 7).step_by(13) {
+				let product = a.mul_exact(*b).unwrap();
+				let expected = a * b;
+				assert_eq!(product.to_f64().to_bits(), expected.to_bits(), "{a} * {b}");
+			}
+		}
+	}
+
+	#[test]
+	fn test_to_f64_rounded() {
+		let x = 1.0 + f64::EPSILON;
+		let product = x.mul_exact(x).unwrap(); // `1 + 2^-51 + 2^-104`
+		assert_eq!(
+			product.to_f64_rounded(RoundingMode::Floor),
+			1.0 + 2.0 * f64::EPSILON
+		);
+		assert_eq!(
+			product.to_f64_rounded(RoundingMode::Ceil),
+			1.0 + 3.0 * f64::EPSILON
+		);
+
+		let product = (-x).mul_exact(x).unwrap();
+		assert_eq!(
+			product.to_f64_rounded(RoundingMode::Floor),
+			-1.0 - 3.0 * f64::EPSILON
+		);
+		assert_eq!(
+			product.to_f64_rounded(RoundingMode::TowardZero),
+			-1.0 - 2.0 * f64::EPSILON
+		);
+
+		let product = f64::MAX.mul_exact(2.0).unwrap();
+		assert_eq!(product.to_f64(), f64::INFINITY);
+		assert_eq!(product.to_f64_rounded(RoundingMode::TowardZero), f64::MAX);
+		assert_eq!(product.to_f64_rounded(RoundingMode::Floor), f64::MAX);
+
+		let product = f64::from_bits(1).mul_exact(-0.5).unwrap();
+		assert_eq!(product.to_f64().to_bits(), (-0.0f64).to_bits());
+		assert_eq!(
+			product.to_f64_rounded(RoundingMode::Floor),
+			-f64::from_bits(1)
+		);
+	}
+
+	#[test]
+	fn test_cmp() {
+		let product = 0.1f64.mul_exact(3.0).unwrap();
+		assert!(product > 0.3);
+		assert!(product < 0.30000000000000004);
+		assert!(product > 0);
+		assert!(product < 1);
+
+		let product = 1e10f64.mul_exact(1e10).unwrap();
+		assert!(product == 100_000_000_000_000_000_000i128);
+		assert!(product == 1e20);
+		assert_eq!(product.cmp_int(100_000_000_000_000_000_001), Ordering::Less);
+
+		let product = (-1.5f64).mul_exact(2.0).unwrap();
+		assert_eq!(product.cmp_int(-3), Ordering::Equal);
+		assert_eq!(product.cmp_int(-4), Ordering::Greater);
+		assert_eq!(product.cmp_int(0), Ordering::Less);
+		assert_eq!(product, 3.0f64.mul_exact(-1.0).unwrap());
+		assert!(product < 0.5f64.mul_exact(-5.0).unwrap());
+
+		let zero = 0.0f64.mul_exact(-1.0).unwrap();
+		assert_eq!(zero, 0.0f64.mul_exact(1.0).unwrap());
+		assert!(zero == 0);
+		assert!(zero == -0.0);
+
+		let huge = f64::MAX.mul_exact(f64::MAX).unwrap();
+		assert!(huge > i128::MAX);
+		assert!(huge > f64::MAX);
+		assert!(huge < f64::INFINITY);
+		assert_eq!(huge.partial_cmp_f64(f64::NAN), None);
+	}
+
+	#[test]
+	fn test_rounding() {
+		let vectors = [
+			(2.5f64, 1.0f64, 2i128, 3i128, 2i128, 0.5f64),
+			(-2.5, 1.0, -3, -2, -2, -0.5),
+			(3.0, 1.0, 3, 3, 3, 0.0),
+			(-0.1, 1e-300, -1, 0, 0, -1e-301),
+		];
+
+		for (a, b, floor, ceil, trunc, fract) in vectors {
+			let product = a.mul_exact(b).unwrap();
+			assert!(product.floor() == floor);
+			assert!(product.ceil() == ceil);
+			assert!(product.trunc() == trunc);
+			assert_eq!(product.fract().to_f64(), fract);
+		}
+
+		let product = 3.25f32.mul_exact(-2.0).unwrap();
+		assert_eq!(product.to_int::<i8>(RoundingMode::NearestAway).unwrap(), -7);
+		assert_eq!(product.to_int::<i8>(RoundingMode::NearestEven).unwrap(), -6);
+	}
+}
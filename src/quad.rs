@@ -0,0 +1,368 @@
+use core::num::FpCategory;
+
+use crate::{
+	decomposed::classify_product,
+	int::Integer,
+	rounding::{Remainder, RoundingMode},
+	BigInt, Decomposed, Error,
+};
+
+/// IEEE 754 binary128 value, stored as its raw bits.
+///
+/// The product of two binary128 significands takes up to 226 bits, which
+/// does not fit in an [`ExactProduct`](crate::ExactProduct). This type
+/// does not implement [`FloatMulToInt`](crate::FloatMulToInt), and only
+/// provides its exact [`mul_to_int`](Self::mul_to_int),
+/// [`mul_to_int_rounded`](Self::mul_to_int_rounded),
+/// [`mul_to_int_as`](Self::mul_to_int_as) and
+/// [`mul_to_int_big`](Self::mul_to_int_big) methods.
+///
+/// ```
+/// use fmul_to_int::F128Bits;
+///
+/// // `1 + 2^-112`, the successor of `1`.
+/// let a = F128Bits::from_bits(0x3fff_0000_0000_0000_0000_0000_0000_0001);
+/// let b = F128Bits::from(2f64.powi(120));
+/// assert_eq!(a.mul_to_int(b).unwrap(), (1i128 << 120) + 256);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct F128Bits(u128);
+
+impl F128Bits {
+	/// Creates a value from its raw bits.
+	pub const fn from_bits(bits: u128) -> Self {
+		Self(bits)
+	}
+
+	/// Returns the raw bits of the value.
+	pub const fn to_bits(self) -> u128 {
+		self.0
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result *without approximation*.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into an `i128`.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	pub fn mul_to_int(self, other: Self) -> Result<i128, Error> {
+		self.mul_to_int_rounded(other, RoundingMode::TowardZero)
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and rounds the result
+	/// to an integer *without approximation* using the given rounding
+	/// `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into an `i128`.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	pub fn mul_to_int_rounded(self, other: Self, mode: RoundingMode) -> Result<i128, Error> {
+		WideProduct::new(self, other)?.to_int(mode)
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result *without approximation* as an integer of
+	/// type `T`.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into `T`.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the product is not finite.
+	pub fn mul_to_int_as<T: Integer>(self, other: Self) -> Result<T, Error> {
+		WideProduct::new(self, other)?.to_int(RoundingMode::TowardZero)
+	}
+
+	/// Multiplies the two input numbers `a` and `b`, and returns the
+	/// integer part of the result *without approximation* as an arbitrary
+	/// precision integer.
+	/// The fractional part is truncated.
+	///
+	/// This function never overflows. An [`Error::NotANumber`] or
+	/// [`Error::Infinite`] error is returned if the product is not finite.
+	pub fn mul_to_int_big(self, other: Self) -> Result<BigInt, Error> {
+		Ok(WideProduct::new(self, other)?.to_big())
+	}
+}
+
+impl From<f64> for F128Bits {
+	/// Converts a `f64` into a binary128 value, without approximation.
+	fn from(value: f64) -> Self {
+		/// Number of explicit significand bits of `f64`.
+		const F64_FRACTION_BITS: u32 = f64::MANTISSA_DIGITS - 1;
+
+		/// Number of explicit significand bits of binary128.
+		const FRACTION_BITS: u32 = 112;
+
+		let d = Decomposed::<f64>::new(value);
+		let sign = (d.is_negative() as u128) << 127;
+		let payload = (d.significand() as u128) << (FRACTION_BITS - F64_FRACTION_BITS);
+		match d.category() {
+			FpCategory::Zero => Self(sign),
+			FpCategory::Infinite | FpCategory::Nan => {
+				Self(sign | (0x7fff << FRACTION_BITS) | payload)
+			}
+			_ => {
+				// Normalizes subnormal `f64` values, which are all normal
+				// binary128 values.
+				let shift = d.significand().leading_zeros() - (u64::BITS - f64::MANTISSA_DIGITS);
+				let exponent = (d.exponent() as i32 - shift as i32 + 16383) as u128;
+				let fraction = (payload << shift) & ((1 << FRACTION_BITS) - 1);
+				Self(sign | (exponent << FRACTION_BITS) | fraction)
+			}
+		}
+	}
+}
+
+impl From<f32> for F128Bits {
+	/// Converts a `f32` into a binary128 value, without approximation.
+	fn from(value: f32) -> Self {
+		Self::from(value as f64)
+	}
+}
+
+/// Exact product of two binary128 values.
+///
+/// The absolute value of the product is `significand * 2^exponent`, where
+/// the significand is a 256-bit integer stored as little-endian 64-bit
+/// limbs.
+struct WideProduct {
+	/// Sign bit.
+	negative: bool,
+
+	/// Significand.
+	significand: [u64; 4],
+
+	/// Binary exponent.
+	exponent: i32,
+}
+
+impl WideProduct {
+	/// Computes the exact product of `a` and `b`.
+	fn new(a: F128Bits, b: F128Bits) -> Result<Self, Error> {
+		let (a, b) = (
+			Decomposed::<F128Bits>::new(a),
+			Decomposed::<F128Bits>::new(b),
+		);
+		classify_product(a.category(), b.category())?;
+		let (a, b) = (a.to_product(), b.to_product());
+		let a_limbs = [a.significand as u64, (a.significand >> 64) as u64];
+		let b_limbs = [b.significand as u64, (b.significand >> 64) as u64];
+
+		let mut significand = [0u64; 4];
+		for (i, a) in a_limbs.iter().enumerate() {
+			let mut carry = 0u128;
+			for (j, b) in b_limbs.iter().enumerate() {
+				let value = *a as u128 * *b as u128 + significand[i + j] as u128 + carry;
+				significand[i + j] = value as u64;
+				carry = value >> 64;
+			}
+			significand[i + 2] = carry as u64;
+		}
+
+		Ok(Self {
+			negative: a.negative ^ b.negative,
+			significand,
+			exponent: a.exponent + b.exponent,
+		})
+	}
+
+	/// Returns the number of significant bits of the significand.
+	fn bits(&self) -> i32 {
+		match self.significand.iter().rposition(|limb| *limb != 0) {
+			Some(i) => (i as i32 + 1) * 64 - self.significand[i].leading_zeros() as i32,
+			None => 0,
+		}
+	}
+
+	/// Returns the bit of the significand at position `i`.
+	fn bit(&self, i: u32) -> bool {
+		i < 256 && (self.significand[i as usize / 64] >> (i % 64)) & 1 == 1
+	}
+
+	/// Checks if any bit of the significand below position `i` is set.
+	fn any_below(&self, i: u32) -> bool {
+		let (words, bits) = ((i as usize / 64).min(4), i % 64);
+		self.significand[..words].iter().any(|limb| *limb != 0)
+			|| (words < 4 && self.significand[words] & ((1 << bits) - 1) != 0)
+	}
+
+	/// Returns the significand shifted right by `shift` bits.
+	fn shr(&self, shift: u32) -> [u64; 4] {
+		let (words, bits) = (shift as usize / 64, shift % 64);
+		let mut result = [0u64; 4];
+		for (i, limb) in result.iter_mut().enumerate() {
+			let low = self.significand.get(i + words).copied().unwrap_or(0);
+			let high = self.significand.get(i + words + 1).copied().unwrap_or(0);
+			*limb = if bits == 0 {
+				low
+			} else {
+				(low >> bits) | (high << (64 - bits))
+			};
+		}
+
+		result
+	}
+
+	/// Splits the absolute value of the product into its integer part and
+	/// the position of its fractional part.
+	///
+	/// Returns `None` if the integer part does not fit in a `u128`.
+	fn split(&self) -> Option<(u128, Remainder)> {
+		if self.bits() + self.exponent > 128 {
+			None
+		} else if self.exponent >= 0 {
+			// The significand fits in 128 bits.
+			let significand = self.significand[0] as u128 | (self.significand[1] as u128) << 64;
+			Some((significand << self.exponent, Remainder::Zero))
+		} else {
+			let shift = self.exponent.unsigned_abs();
+			let integer = self.shr(shift);
+			let remainder = Remainder::from_bits(self.bit(shift - 1), self.any_below(shift - 1));
+
+			Some((integer[0] as u128 | (integer[1] as u128) << 64, remainder))
+		}
+	}
+
+	/// Rounds the product to an integer of type `T` using the given
+	/// rounding `mode`.
+	fn to_int<T: Integer>(&self, mode: RoundingMode) -> Result<T, Error> {
+		self.split()
+			.and_then(|(integer, remainder)| mode.round(self.negative, integer, remainder))
+			.and_then(|magnitude| T::from_magnitude(self.negative, magnitude))
+			.ok_or(Error::Overflow {
+				negative: self.negative,
+			})
+	}
+
+	/// Returns the integer part of the product, truncating the fractional
+	/// part.
+	fn to_big(&self) -> BigInt {
+		if self.exponent >= 0 {
+			let exponent = self.exponent as usize;
+			let (words, bits) = (exponent / 64, exponent % 64);
+
+			let mut limbs = vec![0; words + 5];
+			for (i, limb) in self.significand.iter().enumerate() {
+				limbs[words + i] |= limb << bits;
+				if bits != 0 {
+					limbs[words + i + 1] = limb >> (64 - bits);
+				}
+			}
+
+			BigInt::from_limbs(self.negative, limbs)
+		} else {
+			let shift = self.exponent.unsigned_abs();
+			BigInt::from_limbs(self.negative, self.shr(shift).to_vec())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Error, F128Bits, FloatMulToInt, RoundingMode};
+
+	#[test]
+	fn test_from_f64() {
+		let vectors = [
+			(0.0f64, 0u128),
+			(-0.0, 1 << 127),
+			(1.0, 0x3fff << 112),
+			(-3.0, 0xc000_8000_0000_0000_0000_0000_0000_0000),
+			(f64::from_bits(1), (16383 - 1074) << 112),
+			(f64::INFINITY, 0x7fff << 112),
+		];
+
+		for (value, bits) in vectors {
+			assert_eq!(F128Bits::from(value).to_bits(), bits);
+		}
+	}
+
+	#[test]
+	fn test_f64_values() {
+		let values = [
+			0.0f64,
+			-0.0,
+			0.5,
+			-1.5,
+			0.1,
+			3.0,
+			-7.1,
+			1e10,
+			-1e20,
+			2f64.powi(100),
+			2f64.powi(-100),
+			f64::MAX,
+			f64::MIN_POSITIVE,
+			f64::from_bits(1),
+			f64::INFINITY,
+			f64::NEG_INFINITY,
+			f64::NAN,
+		];
+
+		for a in values {
+			for b in values {
+				let (qa, qb) = (F128Bits::from(a), F128Bits::from(b));
+				assert_eq!(qa.mul_to_int(qb), a.mul_to_int(b));
+				assert_eq!(qa.mul_to_int_big(qb), a.mul_to_int_big(b));
+				assert_eq!(qa.mul_to_int_as::<i8>(qb), a.mul_to_int_as::<i8>(b));
+				assert_eq!(
+					qa.mul_to_int_rounded(qb, RoundingMode::NearestEven),
+					a.mul_to_int_rounded(b, RoundingMode::NearestEven)
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_wide() {
+		use RoundingMode::*;
+
+		// Largest value below `2`, that is `2 - 2^-112`.
+		let a = F128Bits::from_bits(0x3fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff);
+		assert_eq!(a.mul_to_int(a).unwrap(), 3);
+		assert_eq!(a.mul_to_int_rounded(a, Ceil).unwrap(), 4);
+		assert_eq!(a.mul_to_int_rounded(a, NearestEven).unwrap(), 4);
+
+		// `(2 - 2^-112) * 2^125 = 2^126 - 2^13`.
+		let b = F128Bits::from(2f64.powi(125));
+		assert_eq!(a.mul_to_int(b).unwrap(), (1 << 126) - (1 << 13));
+
+		// `2^126 + 2^14`, then doubled.
+		let c = F128Bits::from_bits(0x407d_0000_0000_0000_0000_0000_0000_0001);
+		assert_eq!(
+			c.mul_to_int(F128Bits::from(1.0)).unwrap(),
+			(1 << 126) + (1 << 14)
+		);
+		assert_eq!(
+			c.mul_to_int(F128Bits::from(-2.0)),
+			Err(Error::Overflow { negative: true })
+		);
+
+		// `(1 + 2^-112) * 0.5` has a tiny fractional part above one half.
+		let d = F128Bits::from_bits(0x3fff_0000_0000_0000_0000_0000_0000_0001);
+		let half = F128Bits::from(0.5);
+		assert_eq!(d.mul_to_int_rounded(half, NearestEven).unwrap(), 1);
+		assert_eq!(half.mul_to_int_rounded(half, NearestAway).unwrap(), 0);
+
+		// The largest finite value, squared.
+		let max = F128Bits::from_bits(0x7ffe_ffff_ffff_ffff_ffff_ffff_ffff_ffff);
+		assert_eq!(max.mul_to_int_big(max).unwrap().bits(), 32768);
+		assert_eq!(
+			max.mul_to_int(max),
+			Err(Error::Overflow { negative: false })
+		);
+
+		// The smallest subnormal value, times the largest finite value, is
+		// `2^-110 - 2^-223`.
+		let min = F128Bits::from_bits(1);
+		assert_eq!(min.mul_to_int_rounded(max, Floor).unwrap(), 0);
+		assert_eq!(min.mul_to_int_rounded(max, Ceil).unwrap(), 1);
+	}
+}
@@ -0,0 +1,105 @@
+/// Rounding mode used to convert an exact real result into an integer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+	/// Round toward zero, truncating the fractional part.
+	///
+	/// This is the rounding mode used by
+	/// [`mul_to_int`](crate::FloatMulToInt::mul_to_int).
+	#[default]
+	TowardZero,
+
+	/// Round toward negative infinity.
+	Floor,
+
+	/// Round toward positive infinity.
+	Ceil,
+
+	/// Round to the nearest integer, ties to even.
+	NearestEven,
+
+	/// Round to the nearest integer, ties away from zero.
+	NearestAway,
+}
+
+impl RoundingMode {
+	/// Rounds the absolute value of a number, given its integer part
+	/// `magnitude` and the position of its fractional part `remainder`.
+	///
+	/// Returns `None` if the rounded magnitude does not fit in a `u128`.
+	pub(crate) const fn round(
+		self,
+		negative: bool,
+		magnitude: u128,
+		remainder: Remainder,
+	) -> Option<u128> {
+		let round_up = match remainder {
+			Remainder::Zero => false,
+			_ => match self {
+				Self::TowardZero => false,
+				Self::Floor => negative,
+				Self::Ceil => !negative,
+				Self::NearestEven => match remainder {
+					Remainder::Half => magnitude & 1 == 1,
+					Remainder::AboveHalf => true,
+					_ => false,
+				},
+				Self::NearestAway => matches!(remainder, Remainder::Half | Remainder::AboveHalf),
+			},
+		};
+
+		if round_up {
+			magnitude.checked_add(1)
+		} else {
+			Some(magnitude)
+		}
+	}
+}
+
+/// Position of a fractional part in the `[0, 1)` interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Remainder {
+	/// Fractional part is zero.
+	Zero,
+
+	/// Fractional part is in `(0, 0.5)`.
+	BelowHalf,
+
+	/// Fractional part is exactly `0.5`.
+	Half,
+
+	/// Fractional part is in `(0.5, 1)`.
+	AboveHalf,
+}
+
+impl Remainder {
+	/// Compares the fraction `numerator / 2^shift` against one half.
+	///
+	/// The `numerator` must be lower than `2^shift`.
+	pub(crate) const fn of_shifted(numerator: u128, shift: u32) -> Self {
+		if numerator == 0 {
+			Self::Zero
+		} else if shift > 128 {
+			Self::BelowHalf
+		} else {
+			let half = 1u128 << (shift - 1);
+			if numerator < half {
+				Self::BelowHalf
+			} else if numerator == half {
+				Self::Half
+			} else {
+				Self::AboveHalf
+			}
+		}
+	}
+
+	/// Builds the position of a fraction from its first bit `half`, and
+	/// whether any of the following bits is set.
+	pub(crate) const fn from_bits(half: bool, sticky: bool) -> Self {
+		match (half, sticky) {
+			(false, false) => Self::Zero,
+			(false, true) => Self::BelowHalf,
+			(true, false) => Self::Half,
+			(true, true) => Self::AboveHalf,
+		}
+	}
+}
@@ -0,0 +1,145 @@
+use crate::{int::Integer, mul_int::FloatMulIntToInt, rounding::RoundingMode, Error};
+
+/// Float type implementing the `to_scaled_int` function.
+pub trait FloatToScaledInt: Sized {
+	/// Integer output type.
+	type Output: Integer;
+
+	/// Converts the float into a fixed-point integer with `decimals`
+	/// decimal places, returning the integer part of `value * 10^decimals`
+	/// *without approximation*.
+	/// The fractional part is truncated.
+	///
+	/// The scale `10^decimals` is computed with integers, and never rounded
+	/// through a float.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the value is not finite, and an [`Error::ScaleTooLarge`] error if
+	/// `decimals` is greater than `38`.
+	///
+	/// ```
+	/// use fmul_to_int::FloatToScaledInt;
+	///
+	/// // `19.99` is slightly below `1999/100` in `f64`.
+	/// assert_eq!(19.99f64.to_scaled_int(2).unwrap(), 1998i128);
+	/// ```
+	fn to_scaled_int(self, decimals: u32) -> Result<Self::Output, Error> {
+		self.to_scaled_int_rounded(decimals, RoundingMode::TowardZero)
+	}
+
+	/// Converts the float into a fixed-point integer with `decimals`
+	/// decimal places, rounding `value * 10^decimals` to an integer
+	/// *without approximation* using the given rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into the [`Self::Output`] type.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the value is not finite, and an [`Error::ScaleTooLarge`] error if
+	/// `decimals` is greater than `38`.
+	///
+	/// ```
+	/// use fmul_to_int::{FloatToScaledInt, RoundingMode};
+	///
+	/// assert_eq!(19.99f64.to_scaled_int_rounded(2, RoundingMode::NearestEven).unwrap(), 1999i128);
+	/// ```
+	fn to_scaled_int_rounded(
+		self,
+		decimals: u32,
+		mode: RoundingMode,
+	) -> Result<Self::Output, Error>;
+}
+
+/// Returns `10^decimals`, if it fits in a `u128`.
+fn scale(decimals: u32) -> Result<u128, Error> {
+	10u128.checked_pow(decimals).ok_or(Error::ScaleTooLarge)
+}
+
+impl FloatToScaledInt for f32 {
+	type Output = i64;
+
+	fn to_scaled_int_rounded(self, decimals: u32, mode: RoundingMode) -> Result<i64, Error> {
+		self.mul_int_to_int_rounded(scale(decimals)?, mode)
+	}
+}
+
+impl FloatToScaledInt for f64 {
+	type Output = i128;
+
+	fn to_scaled_int_rounded(self, decimals: u32, mode: RoundingMode) -> Result<i128, Error> {
+		self.mul_int_to_int_rounded(scale(decimals)?, mode)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Error, FloatToScaledInt, RoundingMode};
+
+	#[test]
+	fn test_f64() {
+		let vectors = [
+			(19.99f64, 2, 1998i128, 1999i128),
+			(-19.99, 2, -1998, -1999),
+			(0.1, 0, 0, 0),
+			(0.5, 0, 0, 0),
+			(1.5, 0, 1, 2),
+			(0.00000001, 8, 1, 1), // One satoshi.
+			(1.23456789, 9, 1_234_567_889, 1_234_567_890),
+			(1.0, 38, 10i128.pow(38), 10i128.pow(38)),
+			(
+				-1.7,
+				38,
+				-169_999_999_999_999_995_559_107_901_499_373_838_305,
+				-169_999_999_999_999_995_559_107_901_499_373_838_305,
+			),
+			(1e-38, 38, 0, 1),
+		];
+
+		for (value, decimals, truncated, nearest) in vectors {
+			assert_eq!(value.to_scaled_int(decimals).unwrap(), truncated);
+			assert_eq!(
+				value
+					.to_scaled_int_rounded(decimals, RoundingMode::NearestEven)
+					.unwrap(),
+				nearest
+			);
+		}
+
+		assert_eq!(
+			1.8f64.to_scaled_int(38),
+			Err(Error::Overflow { negative: false })
+		);
+		assert_eq!(f64::NAN.to_scaled_int(2), Err(Error::NotANumber));
+	}
+
+	#[test]
+	fn test_f32() {
+		assert_eq!(19.99f32.to_scaled_int(2).unwrap(), 1998i64);
+		assert_eq!(
+			19.99f32
+				.to_scaled_int_rounded(2, RoundingMode::NearestAway)
+				.unwrap(),
+			1999i64
+		);
+		assert_eq!(1.5f32.to_scaled_int(18).unwrap(), 1_500_000_000_000_000_000);
+		assert_eq!(
+			1e-20f32
+				.to_scaled_int_rounded(38, RoundingMode::Floor)
+				.unwrap(),
+			999_999_968_265_522_538
+		);
+	}
+
+	#[test]
+	fn test_too_many_decimals() {
+		assert_eq!(1.0f64.to_scaled_int(39), Err(Error::ScaleTooLarge));
+		assert_eq!(0.0f64.to_scaled_int(39), Err(Error::ScaleTooLarge));
+		assert_eq!(
+			1.0f32.to_scaled_int_rounded(u32::MAX, RoundingMode::Floor),
+			Err(Error::ScaleTooLarge)
+		);
+	}
+}
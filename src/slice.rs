@@ -0,0 +1,216 @@
+use core::fmt;
+
+use crate::{Error, FloatMulToInt};
+
+/// Error returned by the slice functions, locating the first element whose
+/// product could not be converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SliceError {
+	/// Index of the first failing element.
+	pub index: usize,
+
+	/// Error of the failing element.
+	pub error: Error,
+}
+
+impl fmt::Display for SliceError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} at index {}", self.error, self.index)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SliceError {}
+
+/// Multiplies the elements of `a` and `b` pairwise, and stores the integer
+/// part of each product in `out`, *without approximation*.
+///
+/// This is equivalent to calling
+/// [`mul_to_int`](FloatMulToInt::mul_to_int) on each pair. The conversion
+/// stops at the first failing element, which is reported along with its
+/// error. Previous elements of `out` are written, following elements are
+/// left untouched.
+///
+/// For `f64`, the products are computed by a vectorized kernel, using
+/// AVX-512 or AVX2 on `x86_64` and NEON on `aarch64` when the CPU supports
+/// them. Its results are identical to those of the scalar path.
+///
+/// # Panics
+///
+/// Panics if the three slices do not have the same length.
+///
+/// ```
+/// use fmul_to_int::mul_to_int_slice;
+///
+/// let prices = [1.5, 2.25, 10.0];
+/// let quantities = [4.0, 3.0, 0.1];
+/// let mut out = [0i128; 3];
+/// mul_to_int_slice(&prices, &quantities, &mut out).unwrap();
+/// assert_eq!(out, [6, 6, 1]);
+/// ```
+pub fn mul_to_int_slice<F: FloatMulToInt>(
+	a: &[F],
+	b: &[F],
+	out: &mut [F::Output],
+) -> Result<(), SliceError> {
+	assert_eq!(a.len(), b.len(), "input slices have different lengths");
+	assert_eq!(a.len(), out.len(), "output slice has a different length");
+
+	F::mul_slice(a, b, out)
+}
+
+/// Multiplies every element of `a` by the scalar `b`, and stores the
+/// integer part of each product in `out`, *without approximation*.
+///
+/// The scalar is decomposed only once. As with [`mul_to_int_slice`], the
+/// conversion stops at the first failing element, which is reported along
+/// with its error, and `f64` products are computed by a vectorized kernel.
+///
+/// # Panics
+///
+/// Panics if `a` and `out` do not have the same length.
+///
+/// ```
+/// use fmul_to_int::mul_to_int_slice_scalar;
+///
+/// let prices = [1.5, 2.25, 10.0];
+/// let mut cents = [0i128; 3];
+/// mul_to_int_slice_scalar(&prices, 100.0, &mut cents).unwrap();
+/// assert_eq!(cents, [150, 225, 1000]);
+/// ```
+pub fn mul_to_int_slice_scalar<F: FloatMulToInt>(
+	a: &[F],
+	b: F,
+	out: &mut [F::Output],
+) -> Result<(), SliceError> {
+	assert_eq!(a.len(), out.len(), "output slice has a different length");
+
+	F::mul_slice_scalar(a, b, out)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		mul_to_int_slice, mul_to_int_slice_scalar, test_utils::xorshift, Error, FloatMulToInt,
+		SliceError,
+	};
+
+	/// Deterministic pseudo-random `f64` values, mixing raw bit patterns
+	/// with values of moderate magnitude.
+	fn values(n: usize) -> Vec<f64> {
+		xorshift(0x9e37_79b9_7f4a_7c15)
+			.take(n)
+			.enumerate()
+			.map(|(i, bits)| {
+				if i % 2 == 0 {
+					f64::from_bits(bits)
+				} else {
+					(bits >> 11) as f64 * 2f64.powi((i % 160) as i32 - 120)
+				}
+			})
+			.collect()
+	}
+
+	/// Boundary values around the limits of `i128`.
+	fn boundaries() -> Vec<f64> {
+		let mut result = vec![
+			0.0,
+			-0.0,
+			0.5,
+			-0.5,
+			1.0,
+			-1.0,
+			f64::MAX,
+			f64::MIN_POSITIVE,
+			f64::from_bits(1),
+			f64::INFINITY,
+			f64::NEG_INFINITY,
+			f64::NAN,
+		];
+
+		for exponent in [63, 64, 126, 127, 128] {
+			let x = 2f64.powi(exponent);
+			result.extend([x, -x, x * (1.0 - f64::EPSILON), -x * (1.0 + f64::EPSILON)]);
+		}
+
+		result
+	}
+
+	/// Checks that `out` matches the scalar path, up to the first error.
+	fn check(a: &[f64], b: impl Fn(usize) -> f64, out: &[i128], result: Result<(), SliceError>) {
+		let first_error = (0..a.len()).find_map(|i| a[i].mul_to_int(b(i)).err().map(|e| (i, e)));
+		match first_error {
+			Some((index, error)) => {
+				assert_eq!(result, Err(SliceError { index, error }));
+				for i in 0..index {
+					assert_eq!(out[i], a[i].mul_to_int(b(i)).unwrap());
+				}
+			}
+			None => {
+				assert_eq!(result, Ok(()));
+				for i in 0..a.len() {
+					assert_eq!(out[i], a[i].mul_to_int(b(i)).unwrap());
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_slice() {
+		let inputs = [values(4096), boundaries()];
+		for a in &inputs {
+			for shift in 0..a.len().min(64) {
+				let b: Vec<f64> = a
+					.iter()
+					.cycle()
+					.skip(shift)
+					.take(a.len())
+					.copied()
+					.collect();
+				let mut out = vec![0i128; a.len()];
+				let result = mul_to_int_slice(a, &b, &mut out);
+				check(a, |i| b[i], &out, result);
+			}
+		}
+	}
+
+	#[test]
+	fn test_slice_scalar() {
+		let a = values(4096);
+		for b in boundaries().into_iter().chain(values(64)) {
+			let mut out = vec![0i128; a.len()];
+			let result = mul_to_int_slice_scalar(&a, b, &mut out);
+			check(&a, |_| b, &out, result);
+		}
+
+		// Values of moderate magnitude only, so every element is written.
+		let a: Vec<f64> = values(4096).into_iter().skip(1).step_by(2).collect();
+		let mut out = vec![0i128; a.len()];
+		mul_to_int_slice_scalar(&a, 2f64.powi(-8), &mut out).unwrap();
+		check(&a, |_| 2f64.powi(-8), &out, Ok(()));
+	}
+
+	#[test]
+	fn test_f32() {
+		let a = [0.1f32, 1e10, -3.5];
+		let mut out = [0i64; 3];
+		mul_to_int_slice(&a, &[10.0, 1e10, 2.0], &mut out).unwrap_err();
+		assert_eq!(out[0], 1);
+
+		assert_eq!(
+			mul_to_int_slice_scalar(&a, 1e9, &mut out),
+			Err(SliceError {
+				index: 1,
+				error: Error::Overflow { negative: false }
+			})
+		);
+		assert_eq!(out[0], 100_000_001);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_length_mismatch() {
+		let mut out = [0i128; 2];
+		let _ = mul_to_int_slice(&[1.0, 2.0], &[1.0], &mut out);
+	}
+}
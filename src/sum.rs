@@ -0,0 +1,171 @@
+use crate::{accumulator::Accumulator, rounding::RoundingMode, Decomposed, Error};
+
+/// Exact sum of products of `f64` values.
+///
+/// Every product is added to a wide fixed-point accumulator without any
+/// rounding, so the integer part of the total is exact whatever the number
+/// and magnitude of the terms.
+///
+/// ```
+/// use fmul_to_int::ExactProductSum;
+///
+/// let items = [(3.0, 0.1), (7.0, 0.7), (1.0, -5.2)];
+/// let sum: ExactProductSum = items.into_iter().collect();
+/// assert_eq!(sum.to_int().unwrap(), 0i128);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExactProductSum {
+	/// Sum of the finite products.
+	accumulator: Accumulator,
+
+	/// Whether a NaN product was added.
+	nan: bool,
+
+	/// Whether a positive infinite product was added.
+	positive_infinity: bool,
+
+	/// Whether a negative infinite product was added.
+	negative_infinity: bool,
+}
+
+impl ExactProductSum {
+	/// Creates a new empty sum.
+	pub const fn new() -> Self {
+		Self {
+			accumulator: Accumulator::new(),
+			nan: false,
+			positive_infinity: false,
+			negative_infinity: false,
+		}
+	}
+
+	/// Adds the exact product `a * b` to the sum.
+	pub fn add_product(&mut self, a: f64, b: f64) {
+		let (a, b) = (Decomposed::<f64>::new(a), Decomposed::<f64>::new(b));
+		match a.mul(b) {
+			Ok(product) => self.accumulator.add(product),
+			Err(Error::Infinite) => {
+				if a.is_negative() ^ b.is_negative() {
+					self.negative_infinity = true
+				} else {
+					self.positive_infinity = true
+				}
+			}
+			Err(_) => self.nan = true,
+		}
+	}
+
+	/// Adds `value` to the sum.
+	pub fn add(&mut self, value: f64) {
+		self.add_product(value, 1.0)
+	}
+
+	/// Returns the integer part of the sum *without approximation*.
+	/// The fractional part is truncated.
+	///
+	/// This function returns an [`Error::Overflow`] error if the integer
+	/// part does not fit into an `i128`.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the sum is not finite.
+	pub fn to_int(&self) -> Result<i128, Error> {
+		self.to_int_rounded(RoundingMode::TowardZero)
+	}
+
+	/// Rounds the sum to an integer *without approximation* using the given
+	/// rounding `mode`.
+	///
+	/// This function returns an [`Error::Overflow`] error if the rounded
+	/// result does not fit into an `i128`.
+	///
+	/// An [`Error::NotANumber`] or [`Error::Infinite`] error is returned if
+	/// the sum is not finite.
+	pub fn to_int_rounded(&self, mode: RoundingMode) -> Result<i128, Error> {
+		if self.nan || (self.positive_infinity && self.negative_infinity) {
+			Err(Error::NotANumber)
+		} else if self.positive_infinity || self.negative_infinity {
+			Err(Error::Infinite)
+		} else {
+			self.accumulator.to_int(mode)
+		}
+	}
+}
+
+impl Extend<(f64, f64)> for ExactProductSum {
+	fn extend<T: IntoIterator<Item = (f64, f64)>>(&mut self, iter: T) {
+		for (a, b) in iter {
+			self.add_product(a, b)
+		}
+	}
+}
+
+impl FromIterator<(f64, f64)> for ExactProductSum {
+	fn from_iter<T: IntoIterator<Item = (f64, f64)>>(iter: T) -> Self {
+		let mut result = Self::new();
+		result.extend(iter);
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ExactProductSum;
+	use crate::{Error, RoundingMode};
+
+	#[test]
+	fn test_sum() {
+		let mut sum = ExactProductSum::new();
+		for _ in 0..10 {
+			sum.add_product(0.1, 1.0);
+		}
+
+		// `0.1` is slightly above `1/10` in `f64`.
+		assert_eq!(sum.to_int().unwrap(), 1);
+		assert_eq!(sum.to_int_rounded(RoundingMode::Ceil).unwrap(), 2);
+
+		sum.add(-1.0);
+		assert_eq!(sum.to_int().unwrap(), 0);
+		assert_eq!(sum.to_int_rounded(RoundingMode::Floor).unwrap(), 0);
+		assert_eq!(sum.to_int_rounded(RoundingMode::Ceil).unwrap(), 1);
+
+		let sum: ExactProductSum = [
+			(f64::MAX, 2.0),
+			(1e15, 1e15),
+			(-(f64::MAX), 2.0),
+			(f64::from_bits(1), -1.0),
+		]
+		.into_iter()
+		.collect();
+		assert_eq!(
+			sum.to_int().unwrap(),
+			1_000_000_000_000_000_000_000_000_000_000 - 1
+		);
+		assert_eq!(
+			sum.to_int_rounded(RoundingMode::NearestEven).unwrap(),
+			1_000_000_000_000_000_000_000_000_000_000
+		);
+
+		let sum: ExactProductSum = (0..1000).map(|i| (i as f64, 2f64.powi(120))).collect();
+		assert_eq!(sum.to_int(), Err(Error::Overflow { negative: false }));
+
+		let sum: ExactProductSum = (0..1000)
+			.map(|i| (i as f64 - 500.0, 2f64.powi(100)))
+			.collect();
+		assert_eq!(sum.to_int().unwrap(), -500 << 100);
+	}
+
+	#[test]
+	fn test_errors() {
+		let mut sum = ExactProductSum::new();
+		sum.add_product(f64::INFINITY, -2.0);
+		assert_eq!(sum.to_int(), Err(Error::Infinite));
+		sum.add(f64::NEG_INFINITY);
+		assert_eq!(sum.to_int(), Err(Error::Infinite));
+		sum.add(f64::INFINITY);
+		assert_eq!(sum.to_int(), Err(Error::NotANumber));
+
+		let mut sum = ExactProductSum::new();
+		sum.add_product(0.0, f64::INFINITY);
+		assert_eq!(sum.to_int(), Err(Error::NotANumber));
+	}
+}
@@ -0,0 +1,28 @@
+use core::fmt::Debug;
+
+use crate::rounding::RoundingMode;
+
+/// Deterministic pseudo-random 64-bit words, generated by a xorshift from
+/// the given non-zero `seed`.
+pub fn xorshift(seed: u64) -> impl Iterator<Item = u64> {
+	let mut state = seed;
+	core::iter::repeat_with(move || {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		state
+	})
+}
+
+/// Checks the results of `f` in every rounding mode, given in the order
+/// `TowardZero`, `Floor`, `Ceil`, `NearestEven` and `NearestAway`.
+pub fn check_rounding<T: PartialEq + Debug>(expected: [T; 5], f: impl Fn(RoundingMode) -> T) {
+	use RoundingMode::*;
+
+	for (mode, value) in [TowardZero, Floor, Ceil, NearestEven, NearestAway]
+		.into_iter()
+		.zip(expected)
+	{
+		assert_eq!(f(mode), value, "{mode:?}");
+	}
+}